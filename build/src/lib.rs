@@ -0,0 +1,214 @@
+//! Compiles `.peg` grammar files into Rust source from a build script.
+//!
+//! Grammar files use standard PEG notation: rules are defined with `Name <- expression`, literals
+//! are quoted with `'` or `"`, `[a-z0-9_]` is a character class (`[^...]` negates it), `.` matches
+//! any character, `&` and `!` are lookaheads, `*`, `+` and `?` repeat the preceding expression,
+//! `/` separates ordered alternatives and `#` starts a comment that runs to the end of the line.
+//! Every rule becomes a public type named after it, so rule names must not be Rust keywords.
+
+mod peg;
+
+use {
+    pegast_codegen::grammar::Generator,
+    std::{
+        env,
+        error::Error,
+        fmt::{self, Display, Formatter},
+        fs, io,
+        path::{Path, PathBuf},
+    },
+};
+
+#[derive(Debug)]
+pub enum BuildError {
+    MissingOutDir,
+    Io(PathBuf, io::Error),
+    Grammar(GrammarError),
+}
+
+impl Display for BuildError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            BuildError::MissingOutDir => write!(
+                formatter,
+                "OUT_DIR is not set, grammars must be compiled from a build script"
+            ),
+            BuildError::Io(path, error) => write!(formatter, "{}: {}", path.display(), error),
+            BuildError::Grammar(error) => write!(formatter, "{}", error),
+        }
+    }
+}
+
+impl Error for BuildError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            BuildError::Io(_, error) => Some(error),
+            BuildError::MissingOutDir | BuildError::Grammar(_) => None,
+        }
+    }
+}
+
+impl From<GrammarError> for BuildError {
+    fn from(error: GrammarError) -> Self {
+        BuildError::Grammar(error)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GrammarError {
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl Display for GrammarError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "{}:{}:{}: {}",
+            self.file.display(),
+            self.line,
+            self.column,
+            self.message
+        )
+    }
+}
+
+impl Error for GrammarError {}
+
+pub fn compile_grammar(grammar_path: impl AsRef<Path>) -> Result<PathBuf, BuildError> {
+    let grammar_path = grammar_path.as_ref();
+    let out_dir = env::var_os("OUT_DIR").ok_or(BuildError::MissingOutDir)?;
+    let file_name = grammar_path
+        .file_stem()
+        .map(|stem| Path::new(stem).with_extension("rs"))
+        .unwrap_or_else(|| PathBuf::from("grammar.rs"));
+    let output_path = Path::new(&out_dir).join(file_name);
+
+    compile_grammar_to(grammar_path, &output_path)?;
+
+    Ok(output_path)
+}
+
+pub fn compile_grammar_to(
+    grammar_path: impl AsRef<Path>,
+    output_path: impl AsRef<Path>,
+) -> Result<(), BuildError> {
+    let grammar_path = grammar_path.as_ref();
+    let output_path = output_path.as_ref();
+
+    println!("cargo:rerun-if-changed={}", grammar_path.display());
+
+    let source = fs::read_to_string(grammar_path)
+        .map_err(|error| BuildError::Io(grammar_path.to_owned(), error))?;
+    let generated = generate(grammar_path, &source)?;
+
+    fs::write(output_path, generated).map_err(|error| BuildError::Io(output_path.to_owned(), error))
+}
+
+pub fn generate(grammar_path: impl AsRef<Path>, source: &str) -> Result<String, GrammarError> {
+    let grammar_path = grammar_path.as_ref();
+    let grammar = peg::parse(source).map_err(|error| GrammarError {
+        file: grammar_path.to_owned(),
+        line: error.line,
+        column: error.column,
+        message: error.message,
+    })?;
+    let generated = Generator::new(&grammar).generate(grammar);
+
+    Ok(format!("{}\n", generated))
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::generate, std::path::Path};
+
+    fn error_position(source: &str) -> (usize, usize, String) {
+        let error = generate(Path::new("test.peg"), source).unwrap_err();
+
+        (error.line, error.column, error.message)
+    }
+
+    #[test]
+    fn reports_errors_inside_groups_at_the_error() {
+        let (line, column, message) =
+            error_position("Sum <- Term ((\n'+' / ) Term)*\nTerm <- 'a'\n");
+
+        assert_eq!((line, column), (2, 7));
+        assert_eq!(message, "expected a parsing expression");
+    }
+
+    #[test]
+    fn reports_unexpected_end_of_file_at_the_end() {
+        let (line, column, message) = error_position("Sum <- 'a'\nTerm <- \n\n");
+
+        assert_eq!((line, column), (4, 1));
+        assert_eq!(message, "expected a parsing expression");
+    }
+
+    #[test]
+    fn reports_unterminated_tokens_at_their_start() {
+        let cases = [
+            ("Sum <- 'a\n", (1, 8), "unterminated literal"),
+            ("Sum <- [a-z\n", (1, 8), "unterminated character class"),
+            ("Sum <- []\n", (1, 8), "character class is empty"),
+            ("Sum <- [z-a]\n", (1, 9), "character range is reversed"),
+            ("Sum <- ('a'\n", (2, 1), "expected `)` to close the group"),
+            ("Sum = 'a'\n", (1, 5), "expected `<-` after the rule name"),
+        ];
+
+        for (source, position, message) in cases.iter() {
+            assert_eq!(
+                error_position(source),
+                (position.0, position.1, message.to_string()),
+                "{}",
+                source
+            );
+        }
+    }
+
+    #[test]
+    fn reports_undefined_and_duplicate_rules() {
+        let (line, column, message) = error_position("Sum <- Term '+' Term\nTerm <- Nmber\n");
+
+        assert_eq!((line, column), (2, 9));
+        assert_eq!(message, "rule `Nmber` is not defined");
+
+        let (line, column, message) = error_position("Sum <- 'a'\n\nSum <- 'b'\n");
+
+        assert_eq!((line, column), (3, 1));
+        assert_eq!(message, "rule `Sum` is defined more than once");
+    }
+
+    #[test]
+    fn rejects_rust_keywords_as_rule_names() {
+        let (line, column, message) = error_position("Sum <- type\ntype <- 'a'\n");
+
+        assert_eq!((line, column), (1, 8));
+        assert_eq!(message, "rule name `type` is reserved in Rust");
+    }
+
+    #[test]
+    fn accepts_standard_peg_notation() {
+        let source = "# Sums of numbers\n\
+                      Sum    <- Number ('+' Number)*  # trailing comment\n\
+                      Number <- [0-9]+ / \"\\'\" [^'\\n]* '\\''\n\
+                      Space  <- [ \\t\\r\\n]* !. &Sum?\n";
+        let generated = generate(Path::new("test.peg"), source).unwrap();
+
+        assert!(generated.contains("pub struct Sum"));
+        assert!(generated.contains("pub enum Number"));
+        assert!(generated.contains("pub struct Space"));
+        assert!(generated.contains("const START : char = '0'"));
+        assert!(generated.contains("const LITERAL : & 'static str = \"'\""));
+    }
+
+    #[test]
+    fn generates_code_without_imports() {
+        let generated = generate(Path::new("test.peg"), "Sum <- 'a'\n").unwrap();
+
+        assert!(!generated.contains("use "));
+        assert!(generated.contains("impl pegast :: PegAstNode for Sum"));
+    }
+}
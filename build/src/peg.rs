@@ -0,0 +1,417 @@
+use {
+    pegast_codegen::grammar::{Expression, Grammar, Rule},
+    proc_macro2::Span,
+    std::collections::HashSet,
+    syn::{parse_quote, Ident},
+};
+
+pub struct PegError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+type Result<T> = std::result::Result<T, PegError>;
+
+#[derive(Clone, Copy)]
+struct Location {
+    line: usize,
+    column: usize,
+}
+
+#[derive(Clone)]
+struct Parser<'s> {
+    source: &'s str,
+    offset: usize,
+    location: Location,
+    references: Vec<(String, Location)>,
+}
+
+impl<'s> Parser<'s> {
+    fn new(source: &'s str) -> Self {
+        Parser {
+            source,
+            offset: 0,
+            location: Location { line: 1, column: 1 },
+            references: Vec::new(),
+        }
+    }
+
+    fn parse_grammar(&mut self) -> Result<Grammar> {
+        let mut rules = Vec::new();
+        let mut names = HashSet::new();
+
+        self.skip_spacing();
+
+        if self.peek().is_none() {
+            return Err(self.error("expected a rule definition"));
+        }
+
+        while self.peek().is_some() {
+            let location = self.location;
+            let name = self
+                .identifier()
+                .ok_or_else(|| self.error("expected a rule name"))?;
+
+            self.skip_spacing();
+
+            if !self.consume_arrow() {
+                return Err(self.error("expected `<-` after the rule name"));
+            }
+
+            self.skip_spacing();
+
+            let expression = self.parse_expression()?;
+
+            if !names.insert(name.clone()) {
+                return Err(error_at(
+                    location,
+                    format!("rule `{}` is defined more than once", name),
+                ));
+            }
+
+            rules.push(Rule {
+                attributes: Vec::new(),
+                visibility: parse_quote!(pub),
+                name: type_name(&name, location)?,
+                expression,
+            });
+        }
+
+        if let Some((name, location)) = self
+            .references
+            .iter()
+            .find(|(name, _)| !names.contains(name))
+        {
+            return Err(error_at(
+                *location,
+                format!("rule `{}` is not defined", name),
+            ));
+        }
+
+        Ok(Grammar { rules })
+    }
+
+    fn parse_expression(&mut self) -> Result<Expression> {
+        let mut alternatives = vec![self.parse_sequence()?];
+
+        while self.consume('/') {
+            self.skip_spacing();
+            alternatives.push(self.parse_sequence()?);
+        }
+
+        if alternatives.len() == 1 {
+            Ok(alternatives.remove(0))
+        } else {
+            Ok(Expression::Choice(alternatives))
+        }
+    }
+
+    fn parse_sequence(&mut self) -> Result<Expression> {
+        let mut items = Vec::new();
+
+        while !self.is_sequence_end() {
+            items.push(self.parse_prefixed()?);
+        }
+
+        match items.len() {
+            0 => Err(self.error("expected a parsing expression")),
+            1 => Ok(items.remove(0)),
+            _ => Ok(Expression::Sequence(items)),
+        }
+    }
+
+    fn is_sequence_end(&self) -> bool {
+        match self.peek() {
+            None | Some('/') | Some(')') => true,
+            Some(_) => {
+                let mut lookahead = self.clone();
+
+                lookahead.identifier().is_some() && {
+                    lookahead.skip_spacing();
+                    lookahead.consume_arrow()
+                }
+            }
+        }
+    }
+
+    fn parse_prefixed(&mut self) -> Result<Expression> {
+        if self.consume('&') {
+            self.skip_spacing();
+
+            Ok(Expression::Peek(Box::new(self.parse_suffixed()?)))
+        } else if self.consume('!') {
+            self.skip_spacing();
+
+            Ok(Expression::Not(Box::new(self.parse_suffixed()?)))
+        } else {
+            self.parse_suffixed()
+        }
+    }
+
+    fn parse_suffixed(&mut self) -> Result<Expression> {
+        let mut expression = self.parse_primary()?;
+
+        loop {
+            expression = if self.consume('*') {
+                Expression::ZeroOrMore(Box::new(expression))
+            } else if self.consume('+') {
+                Expression::OneOrMore(Box::new(expression))
+            } else if self.consume('?') {
+                Expression::Optional(Box::new(expression))
+            } else {
+                return Ok(expression);
+            };
+
+            self.skip_spacing();
+        }
+    }
+
+    fn parse_primary(&mut self) -> Result<Expression> {
+        let location = self.location;
+        let expression = match self.peek() {
+            Some('(') => {
+                self.next();
+                self.skip_spacing();
+
+                let expression = self.parse_expression()?;
+
+                if !self.consume(')') {
+                    return Err(self.error("expected `)` to close the group"));
+                }
+
+                expression
+            }
+            Some(quote @ '\'') | Some(quote @ '"') => {
+                self.next();
+                self.parse_literal(quote, location)?
+            }
+            Some('[') => {
+                self.next();
+                self.parse_class(location)?
+            }
+            Some('.') => {
+                self.next();
+                Expression::Any
+            }
+            _ => match self.identifier() {
+                Some(name) => {
+                    let reference = type_name(&name, location)?;
+
+                    self.references.push((name, location));
+
+                    Expression::Rule(reference)
+                }
+                None => return Err(self.error("expected a parsing expression")),
+            },
+        };
+
+        self.skip_spacing();
+
+        Ok(expression)
+    }
+
+    fn parse_literal(&mut self, quote: char, location: Location) -> Result<Expression> {
+        let mut literal = String::new();
+
+        loop {
+            match self.peek() {
+                Some(character) if character == quote => {
+                    self.next();
+
+                    return Ok(Expression::Literal(literal));
+                }
+                Some(_) => literal.push(self.parse_character()?),
+                None => return Err(error_at(location, "unterminated literal")),
+            }
+        }
+    }
+
+    fn parse_class(&mut self, location: Location) -> Result<Expression> {
+        let negated = self.consume('^');
+        let mut alternatives = Vec::new();
+
+        loop {
+            match self.peek() {
+                Some(']') => {
+                    self.next();
+                    break;
+                }
+                Some(_) => {
+                    let range_location = self.location;
+                    let start = self.parse_character()?;
+
+                    if self.peek() == Some('-') && !self.rest().starts_with("-]") {
+                        self.next();
+
+                        let end = self.parse_character()?;
+
+                        if end < start {
+                            return Err(error_at(range_location, "character range is reversed"));
+                        }
+
+                        alternatives.push(Expression::Range(start, end));
+                    } else {
+                        alternatives.push(Expression::Literal(start.to_string()));
+                    }
+                }
+                None => return Err(error_at(location, "unterminated character class")),
+            }
+        }
+
+        let class = match alternatives.len() {
+            0 => return Err(error_at(location, "character class is empty")),
+            1 => alternatives.remove(0),
+            _ => Expression::Choice(alternatives),
+        };
+
+        if negated {
+            Ok(Expression::Sequence(vec![
+                Expression::Not(Box::new(class)),
+                Expression::Any,
+            ]))
+        } else {
+            Ok(class)
+        }
+    }
+
+    fn parse_character(&mut self) -> Result<char> {
+        let location = self.location;
+
+        match self.next() {
+            Some('\\') => match self.next() {
+                Some('n') => Ok('\n'),
+                Some('r') => Ok('\r'),
+                Some('t') => Ok('\t'),
+                Some(escaped @ ('\'' | '"' | '[' | ']' | '-' | '\\')) => Ok(escaped),
+                Some(digit @ '0'..='7') => {
+                    let mut value = digit.to_digit(8).expect("Octal digit");
+
+                    for _ in 0..2 {
+                        match self.peek().and_then(|digit| digit.to_digit(8)) {
+                            Some(digit) if value * 8 + digit <= 0o377 => {
+                                self.next();
+                                value = value * 8 + digit;
+                            }
+                            _ => break,
+                        }
+                    }
+
+                    Ok(char::from_u32(value).expect("Octal escape is at most 0o377"))
+                }
+                _ => Err(error_at(location, "unknown escape sequence")),
+            },
+            Some(character) => Ok(character),
+            None => Err(error_at(location, "unexpected end of input")),
+        }
+    }
+
+    fn identifier(&mut self) -> Option<String> {
+        let length = self
+            .rest()
+            .char_indices()
+            .find(|&(index, character)| {
+                !(character == '_'
+                    || character.is_ascii_alphabetic()
+                    || (index > 0 && character.is_ascii_digit()))
+            })
+            .map(|(index, _)| index)
+            .unwrap_or_else(|| self.rest().len());
+
+        if length == 0 {
+            return None;
+        }
+
+        let identifier = self.rest()[..length].to_owned();
+
+        for _ in 0..length {
+            self.next();
+        }
+
+        Some(identifier)
+    }
+
+    fn skip_spacing(&mut self) {
+        loop {
+            match self.peek() {
+                Some(character) if character.is_whitespace() => {
+                    self.next();
+                }
+                Some('#') => {
+                    while self.peek().is_some_and(|character| character != '\n') {
+                        self.next();
+                    }
+                }
+                _ => return,
+            }
+        }
+    }
+
+    fn consume_arrow(&mut self) -> bool {
+        if self.rest().starts_with("<-") {
+            self.next();
+            self.next();
+            true
+        } else {
+            self.consume('←')
+        }
+    }
+
+    fn consume(&mut self, expected: char) -> bool {
+        if self.peek() == Some(expected) {
+            self.next();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn rest(&self) -> &'s str {
+        &self.source[self.offset..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let character = self.peek()?;
+
+        self.offset += character.len_utf8();
+
+        if character == '\n' {
+            self.location.line += 1;
+            self.location.column = 1;
+        } else {
+            self.location.column += 1;
+        }
+
+        Some(character)
+    }
+
+    fn error(&self, message: &str) -> PegError {
+        error_at(self.location, message)
+    }
+}
+
+pub fn parse(source: &str) -> Result<Grammar> {
+    Parser::new(source).parse_grammar()
+}
+
+fn type_name(name: &str, location: Location) -> Result<Ident> {
+    match syn::parse_str::<Ident>(name) {
+        Ok(_) => Ok(Ident::new(name, Span::call_site())),
+        Err(_) => Err(error_at(
+            location,
+            format!("rule name `{}` is reserved in Rust", name),
+        )),
+    }
+}
+
+fn error_at(location: Location, message: impl Into<String>) -> PegError {
+    PegError {
+        line: location.line,
+        column: location.column,
+        message: message.into(),
+    }
+}
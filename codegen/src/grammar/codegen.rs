@@ -0,0 +1,478 @@
+use {
+    super::parser::{Expression, Grammar, Rule},
+    crate::ParsedType,
+    proc_macro2::{Span, TokenStream},
+    quote::{format_ident, quote},
+    std::collections::{HashMap, HashSet},
+    syn::{Attribute, DeriveInput, Error, Ident, Visibility},
+};
+
+pub struct Generator {
+    values_module: Ident,
+    rule_names: HashSet<String>,
+    direct_references: HashMap<String, Vec<String>>,
+    items: Vec<TokenStream>,
+    values: Vec<TokenStream>,
+    literals: HashMap<String, Ident>,
+    ranges: HashMap<(char, char), Ident>,
+    helper_count: usize,
+}
+
+impl Generator {
+    pub fn new(grammar: &Grammar) -> Self {
+        let rule_names = grammar
+            .rules
+            .iter()
+            .map(|rule| rule.name.to_string().to_lowercase())
+            .collect::<Vec<_>>();
+
+        Generator {
+            values_module: format_ident!("__pegast_{}_values", rule_names.join("_")),
+            rule_names: grammar
+                .rules
+                .iter()
+                .map(|rule| rule.name.to_string())
+                .collect(),
+            direct_references: grammar
+                .rules
+                .iter()
+                .map(|rule| {
+                    let mut references = Vec::new();
+
+                    collect_direct_references(&rule.expression, &mut references);
+
+                    (rule.name.to_string(), references)
+                })
+                .collect(),
+            items: Vec::new(),
+            values: Vec::new(),
+            literals: HashMap::new(),
+            ranges: HashMap::new(),
+            helper_count: 0,
+        }
+    }
+
+    pub fn check_references(&self, grammar: &Grammar) -> syn::Result<()> {
+        let mut references = Vec::new();
+
+        for rule in &grammar.rules {
+            collect_references(&rule.expression, &mut references);
+        }
+
+        match references
+            .into_iter()
+            .find(|reference| !self.rule_names.contains(&reference.to_string()))
+        {
+            Some(reference) => Err(Error::new(
+                reference.span(),
+                format!("rule `{}` is not defined", reference),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    pub fn generate(mut self, grammar: Grammar) -> TokenStream {
+        for rule in grammar.rules {
+            self.generate_rule(rule);
+        }
+
+        let values_module = &self.values_module;
+        let items = &self.items;
+        let values = &self.values;
+
+        quote! {
+            #( #items )*
+
+            #[doc(hidden)]
+            #[allow(non_camel_case_types)]
+            pub mod #values_module {
+                #( #values )*
+            }
+        }
+    }
+
+    fn generate_rule(&mut self, rule: Rule) {
+        let Rule {
+            attributes,
+            visibility,
+            name,
+            expression,
+        } = rule;
+        let context = RuleContext {
+            name: name.to_string(),
+            visibility: visibility.clone(),
+        };
+
+        match expression {
+            Expression::Choice(alternatives) => {
+                self.generate_enum(&context, attributes, name, alternatives)
+            }
+            expression => self.generate_struct(&context, attributes, name, expression),
+        }
+    }
+
+    fn generate_struct(
+        &mut self,
+        context: &RuleContext,
+        attributes: Vec<Attribute>,
+        name: Ident,
+        expression: Expression,
+    ) {
+        let visibility = &context.visibility;
+        let fields = self.generate_fields(context, expression);
+
+        self.push_type(quote! {
+            #( #attributes )*
+            #[derive(Clone, Debug)]
+            #visibility struct #name( #( #visibility #fields ),* );
+        });
+    }
+
+    fn generate_enum(
+        &mut self,
+        context: &RuleContext,
+        attributes: Vec<Attribute>,
+        name: Ident,
+        alternatives: Vec<Expression>,
+    ) {
+        let visibility = &context.visibility;
+        let mut variant_names = HashSet::new();
+        let variants = alternatives
+            .into_iter()
+            .enumerate()
+            .map(|(index, alternative)| {
+                let mut variant_name = variant_name(index, &alternative);
+
+                if !variant_names.insert(variant_name.to_string()) {
+                    variant_name = format_ident!("{}{}", variant_name, index);
+                    variant_names.insert(variant_name.to_string());
+                }
+
+                let fields = self.generate_fields(context, alternative);
+
+                quote! { #variant_name( #( #fields ),* ) }
+            })
+            .collect::<Vec<_>>();
+
+        self.push_type(quote! {
+            #( #attributes )*
+            #[derive(Clone, Debug)]
+            #visibility enum #name {
+                #( #variants, )*
+            }
+        });
+    }
+
+    fn generate_fields(
+        &mut self,
+        context: &RuleContext,
+        expression: Expression,
+    ) -> Vec<TokenStream> {
+        match expression {
+            Expression::Sequence(items) => items
+                .into_iter()
+                .map(|item| self.generate_type(context, item))
+                .collect(),
+            expression => vec![self.generate_type(context, expression)],
+        }
+    }
+
+    fn generate_type(&mut self, context: &RuleContext, expression: Expression) -> TokenStream {
+        match expression {
+            Expression::Choice(alternatives) => {
+                let name = self.helper_name(context, "Choice");
+
+                self.generate_enum(context, Vec::new(), name.clone(), alternatives);
+
+                quote! { #name }
+            }
+            Expression::Sequence(items) if items.len() <= 5 => {
+                let types = items
+                    .into_iter()
+                    .map(|item| self.generate_type(context, item));
+
+                quote! { ( #( #types, )* ) }
+            }
+            Expression::Sequence(items) => {
+                let name = self.helper_name(context, "Sequence");
+
+                self.generate_struct(
+                    context,
+                    Vec::new(),
+                    name.clone(),
+                    Expression::Sequence(items),
+                );
+
+                quote! { #name }
+            }
+            Expression::ZeroOrMore(inner) => {
+                let inner = self.generate_type(context, *inner);
+
+                quote! { Vec<#inner> }
+            }
+            Expression::OneOrMore(inner) => {
+                let inner = self.generate_type(context, *inner);
+
+                quote! { pegast::rules::OneOrMore<#inner> }
+            }
+            Expression::Optional(inner) => {
+                let inner = self.generate_type(context, *inner);
+
+                quote! { Option<#inner> }
+            }
+            Expression::Peek(inner) => {
+                let inner = self.generate_type(context, *inner);
+
+                quote! { pegast::rules::Peek<#inner> }
+            }
+            Expression::Not(inner) => {
+                let inner = self.generate_type(context, *inner);
+
+                quote! { pegast::rules::Not<#inner> }
+            }
+            Expression::Rule(name) => {
+                if self.is_recursive(&name.to_string(), &context.name) {
+                    quote! { Box<#name> }
+                } else {
+                    quote! { #name }
+                }
+            }
+            Expression::Literal(literal) => {
+                let value = self.literal_value(literal);
+                let values_module = &self.values_module;
+
+                quote! { pegast::rules::Literal<#values_module::#value> }
+            }
+            Expression::Range(start, end) => {
+                let value = self.range_value(start, end);
+                let values_module = &self.values_module;
+
+                quote! { pegast::rules::Range<#values_module::#value> }
+            }
+            Expression::Any => quote! { pegast::rules::Any },
+        }
+    }
+
+    fn push_type(&mut self, definition: TokenStream) {
+        let derive_input = syn::parse2::<DeriveInput>(definition)
+            .expect("Generated grammar type is not a valid type definition");
+        let mut emitted_definition = derive_input.clone();
+
+        emitted_definition
+            .attrs
+            .retain(|attribute| !attribute.path.is_ident("pegast"));
+
+        let peg_ast_node_impl = ParsedType::from(derive_input).generate_peg_ast_node_impl();
+
+        self.items.push(quote! { #emitted_definition });
+        self.items.push(peg_ast_node_impl);
+    }
+
+    fn helper_name(&mut self, context: &RuleContext, kind: &str) -> Ident {
+        self.helper_count += 1;
+
+        format_ident!("{}{}{}", context.name, kind, self.helper_count)
+    }
+
+    fn literal_value(&mut self, literal: String) -> Ident {
+        if let Some(value) = self.literals.get(&literal) {
+            return value.clone();
+        }
+
+        let value = format_ident!("Literal{}", self.literals.len());
+
+        self.values.push(quote! {
+            #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+            pub enum #value {}
+
+            impl pegast::rules::literal::LiteralValue for #value {
+                const LITERAL: &'static str = #literal;
+            }
+        });
+        self.literals.insert(literal, value.clone());
+
+        value
+    }
+
+    fn range_value(&mut self, start: char, end: char) -> Ident {
+        if let Some(value) = self.ranges.get(&(start, end)) {
+            return value.clone();
+        }
+
+        let value = format_ident!("Range{}", self.ranges.len());
+
+        self.values.push(quote! {
+            #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+            pub enum #value {}
+
+            impl pegast::rules::range::RangeValue for #value {
+                const START: char = #start;
+                const END: char = #end;
+            }
+        });
+        self.ranges.insert((start, end), value.clone());
+
+        value
+    }
+
+    fn is_recursive(&self, reference: &str, rule: &str) -> bool {
+        if !self.rule_names.contains(reference) {
+            return false;
+        }
+
+        let mut visited = HashSet::new();
+        let mut pending = vec![reference];
+
+        while let Some(current) = pending.pop() {
+            if current == rule {
+                return true;
+            }
+
+            if visited.insert(current) {
+                if let Some(references) = self.direct_references.get(current) {
+                    pending.extend(references.iter().map(String::as_str));
+                }
+            }
+        }
+
+        false
+    }
+}
+
+struct RuleContext {
+    name: String,
+    visibility: Visibility,
+}
+
+fn collect_references<'e>(expression: &'e Expression, references: &mut Vec<&'e Ident>) {
+    match expression {
+        Expression::Choice(expressions) | Expression::Sequence(expressions) => {
+            for expression in expressions {
+                collect_references(expression, references);
+            }
+        }
+        Expression::ZeroOrMore(inner)
+        | Expression::OneOrMore(inner)
+        | Expression::Optional(inner)
+        | Expression::Peek(inner)
+        | Expression::Not(inner) => collect_references(inner, references),
+        Expression::Rule(name) => references.push(name),
+        Expression::Literal(_) | Expression::Range(..) | Expression::Any => {}
+    }
+}
+
+fn collect_direct_references(expression: &Expression, references: &mut Vec<String>) {
+    match expression {
+        Expression::Choice(expressions) | Expression::Sequence(expressions) => {
+            for expression in expressions {
+                collect_direct_references(expression, references);
+            }
+        }
+        Expression::OneOrMore(inner) | Expression::Optional(inner) => {
+            collect_direct_references(inner, references)
+        }
+        Expression::Rule(name) => references.push(name.to_string()),
+        Expression::ZeroOrMore(_)
+        | Expression::Peek(_)
+        | Expression::Not(_)
+        | Expression::Literal(_)
+        | Expression::Range(..)
+        | Expression::Any => {}
+    }
+}
+
+fn variant_name(index: usize, alternative: &Expression) -> Ident {
+    match alternative {
+        Expression::Rule(name) => name.clone(),
+        Expression::Literal(literal)
+            if literal.chars().next().is_some_and(char::is_alphabetic)
+                && literal.chars().all(|character| character.is_alphanumeric()) =>
+        {
+            let mut characters = literal.chars();
+            let first = characters
+                .next()
+                .expect("Literal checked to be non-empty")
+                .to_uppercase();
+
+            Ident::new(
+                &first.chain(characters).collect::<String>(),
+                Span::call_site(),
+            )
+        }
+        _ => format_ident!("Alternative{}", index),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::Generator,
+        crate::grammar::Grammar,
+        quote::quote,
+        syn::{parse2, parse_quote},
+    };
+
+    fn generate(grammar: Grammar) -> String {
+        let generator = Generator::new(&grammar);
+
+        generator.check_references(&grammar).unwrap();
+        generator.generate(grammar).to_string()
+    }
+
+    #[test]
+    fn generates_types_with_qualified_trait_paths() {
+        let generated = generate(parse_quote! {
+            pub Sum = Number ("+" Number)*;
+            Number = '0'..='9'+;
+        });
+
+        assert!(generated.contains("pub struct Sum"));
+        assert!(generated.contains("struct Number"));
+        assert!(generated.contains("impl pegast :: PegAstNode for Sum"));
+        assert!(!generated
+            .replace("pegast :: PegAstNode", "")
+            .contains("PegAstNode"));
+    }
+
+    #[test]
+    fn names_values_module_after_every_rule() {
+        let first = generate(parse_quote! { Value = "a" Other; Other = "b"; });
+        let second = generate(parse_quote! { Value = "a" Another; Another = "b"; });
+
+        assert!(first.contains("mod __pegast_value_other_values"));
+        assert!(second.contains("mod __pegast_value_another_values"));
+    }
+
+    #[test]
+    fn reports_undefined_rule_at_its_reference() {
+        let grammar = parse2::<Grammar>(quote! {
+            Sum = Number "+" Missing;
+            Number = '0'..='9'+;
+        })
+        .unwrap();
+        let error = Generator::new(&grammar)
+            .check_references(&grammar)
+            .unwrap_err();
+
+        assert_eq!(error.to_string(), "rule `Missing` is not defined");
+    }
+
+    #[test]
+    fn rejects_duplicate_rules() {
+        let error = parse2::<Grammar>(quote! {
+            Value = "a";
+            Value = "b";
+        })
+        .err()
+        .unwrap();
+
+        assert_eq!(error.to_string(), "rule `Value` is defined more than once");
+    }
+
+    #[test]
+    fn rejects_empty_expressions() {
+        let error = parse2::<Grammar>(quote! { Value = ; }).err().unwrap();
+
+        assert_eq!(error.to_string(), "expected a parsing expression");
+    }
+}
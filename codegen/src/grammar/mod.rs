@@ -0,0 +1,7 @@
+mod codegen;
+mod parser;
+
+pub use self::{
+    codegen::Generator,
+    parser::{Expression, Grammar, Rule},
+};
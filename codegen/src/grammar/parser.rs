@@ -0,0 +1,185 @@
+use syn::{
+    parenthesized,
+    parse::{Parse, ParseStream},
+    token, Attribute, Error, Ident, LitChar, LitStr, Result, Token, Visibility,
+};
+
+pub struct Grammar {
+    pub rules: Vec<Rule>,
+}
+
+pub struct Rule {
+    pub attributes: Vec<Attribute>,
+    pub visibility: Visibility,
+    pub name: Ident,
+    pub expression: Expression,
+}
+
+pub enum Expression {
+    Choice(Vec<Expression>),
+    Sequence(Vec<Expression>),
+    ZeroOrMore(Box<Expression>),
+    OneOrMore(Box<Expression>),
+    Optional(Box<Expression>),
+    Peek(Box<Expression>),
+    Not(Box<Expression>),
+    Rule(Ident),
+    Literal(String),
+    Range(char, char),
+    Any,
+}
+
+impl Parse for Grammar {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut rules = Vec::<Rule>::new();
+
+        while !input.is_empty() {
+            let rule: Rule = input.parse()?;
+
+            if rules.iter().any(|existing| existing.name == rule.name) {
+                return Err(Error::new(
+                    rule.name.span(),
+                    format!("rule `{}` is defined more than once", rule.name),
+                ));
+            }
+
+            rules.push(rule);
+        }
+
+        Ok(Grammar { rules })
+    }
+}
+
+impl Parse for Rule {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attributes = input.call(Attribute::parse_outer)?;
+        let visibility = input.parse()?;
+        let name = input.parse()?;
+
+        input.parse::<Token![=]>()?;
+
+        let expression = parse_choice(input)?;
+
+        if input.peek(Token![;]) {
+            input.parse::<Token![;]>()?;
+        }
+
+        Ok(Rule {
+            attributes,
+            visibility,
+            name,
+            expression,
+        })
+    }
+}
+
+fn parse_choice(input: ParseStream) -> Result<Expression> {
+    let mut alternatives = vec![parse_sequence(input)?];
+
+    while input.peek(Token![/]) {
+        input.parse::<Token![/]>()?;
+        alternatives.push(parse_sequence(input)?);
+    }
+
+    if alternatives.len() == 1 {
+        Ok(alternatives.remove(0))
+    } else {
+        Ok(Expression::Choice(alternatives))
+    }
+}
+
+fn parse_sequence(input: ParseStream) -> Result<Expression> {
+    let mut items = Vec::new();
+
+    while !is_sequence_end(input) {
+        items.push(parse_prefixed(input)?);
+    }
+
+    match items.len() {
+        0 => Err(input.error("expected a parsing expression")),
+        1 => Ok(items.remove(0)),
+        _ => Ok(Expression::Sequence(items)),
+    }
+}
+
+fn is_sequence_end(input: ParseStream) -> bool {
+    input.is_empty()
+        || input.peek(Token![/])
+        || input.peek(Token![;])
+        || input.peek(Token![#])
+        || input.peek(Token![pub])
+        || (input.peek(Ident) && input.peek2(Token![=]) && !input.peek2(Token![==]))
+}
+
+fn parse_prefixed(input: ParseStream) -> Result<Expression> {
+    if input.peek(Token![&]) {
+        input.parse::<Token![&]>()?;
+
+        Ok(Expression::Peek(Box::new(parse_suffixed(input)?)))
+    } else if input.peek(Token![!]) {
+        input.parse::<Token![!]>()?;
+
+        Ok(Expression::Not(Box::new(parse_suffixed(input)?)))
+    } else {
+        parse_suffixed(input)
+    }
+}
+
+fn parse_suffixed(input: ParseStream) -> Result<Expression> {
+    let mut expression = parse_primary(input)?;
+
+    loop {
+        expression = if input.peek(Token![*]) {
+            input.parse::<Token![*]>()?;
+            Expression::ZeroOrMore(Box::new(expression))
+        } else if input.peek(Token![+]) {
+            input.parse::<Token![+]>()?;
+            Expression::OneOrMore(Box::new(expression))
+        } else if input.peek(Token![?]) {
+            input.parse::<Token![?]>()?;
+            Expression::Optional(Box::new(expression))
+        } else {
+            return Ok(expression);
+        };
+    }
+}
+
+fn parse_primary(input: ParseStream) -> Result<Expression> {
+    if input.peek(token::Paren) {
+        let content;
+
+        parenthesized!(content in input);
+
+        let expression = parse_choice(&content)?;
+
+        if !content.is_empty() {
+            return Err(content.error("unexpected token in parenthesized expression"));
+        }
+
+        Ok(expression)
+    } else if input.peek(LitStr) {
+        let literal = input.parse::<LitStr>()?;
+
+        Ok(Expression::Literal(literal.value()))
+    } else if input.peek(LitChar) {
+        let start = input.parse::<LitChar>()?;
+
+        if input.peek(Token![..=]) {
+            input.parse::<Token![..=]>()?;
+
+            let end = input.parse::<LitChar>()?;
+
+            Ok(Expression::Range(start.value(), end.value()))
+        } else {
+            Ok(Expression::Literal(start.value().to_string()))
+        }
+    } else if input.peek(Token![.]) {
+        input.parse::<Token![.]>()?;
+
+        Ok(Expression::Any)
+    } else if input.peek(Ident) {
+        Ok(Expression::Rule(input.parse()?))
+    } else {
+        Err(input.error("expected a parsing expression"))
+    }
+}
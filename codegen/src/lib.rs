@@ -0,0 +1,12 @@
+pub mod grammar;
+mod parsed_attributes;
+mod parsed_fields;
+mod parsed_generics;
+mod parsed_type;
+mod parsed_variants;
+
+pub use self::parsed_type::ParsedType;
+use self::{
+    parsed_attributes::ParsedAttributes, parsed_fields::ParsedFields,
+    parsed_generics::ParsedGenerics, parsed_variants::ParsedVariants,
+};
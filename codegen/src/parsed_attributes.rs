@@ -0,0 +1,62 @@
+use {
+    proc_macro2::Span,
+    std::collections::{HashMap, HashSet},
+    syn::{Attribute, Ident, Lit, Meta},
+};
+
+pub struct ParsedAttributes {
+    key_value_pairs: HashMap<String, Lit>,
+    flags: HashSet<String>,
+}
+
+impl<T> From<T> for ParsedAttributes
+where
+    T: IntoIterator<Item = Attribute>,
+{
+    fn from(attributes: T) -> Self {
+        let mut key_value_pairs = HashMap::new();
+        let mut flags = HashSet::new();
+        let pegast_ident = Ident::new("pegast", Span::call_site());
+        let pegast_attributes = attributes
+            .into_iter()
+            .filter(|attribute| attribute.path.is_ident(&pegast_ident));
+
+        for attribute in pegast_attributes {
+            match attribute.parse_args::<Meta>() {
+                Ok(Meta::NameValue(meta)) => {
+                    let key = meta
+                        .path
+                        .get_ident()
+                        .expect("Invalid pegast attribute")
+                        .to_string();
+
+                    key_value_pairs.insert(key, meta.lit);
+                }
+                Ok(Meta::Path(path)) => {
+                    let flag = path
+                        .get_ident()
+                        .expect("Invalid pegast attribute")
+                        .to_string();
+
+                    flags.insert(flag);
+                }
+                _ => panic!("Invalid pegast attribute"),
+            }
+        }
+
+        ParsedAttributes {
+            key_value_pairs,
+            flags,
+        }
+    }
+}
+
+impl ParsedAttributes {
+    pub fn get_value(&self, key: &str) -> Option<&Lit> {
+        self.key_value_pairs.get(key)
+    }
+
+    pub fn has_flag(&self, flag: &str) -> bool {
+        self.flags.contains(flag)
+    }
+}
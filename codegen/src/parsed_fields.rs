@@ -0,0 +1,429 @@
+use {
+    crate::ParsedAttributes,
+    proc_macro2::TokenStream,
+    quote::quote,
+    syn::{spanned::Spanned as _, Field, Fields, Ident, Index, Member, Type},
+};
+
+pub struct ParsedFields {
+    fields: Vec<ParsedField>,
+    field_type: FieldType,
+}
+
+impl ParsedFields {
+    pub fn new(fields: Fields) -> Self {
+        match fields {
+            Fields::Unit => panic!("Can't derive(PegAstNode) on a unit struct"),
+            Fields::Named(fields) => ParsedFields {
+                fields: fields
+                    .named
+                    .into_iter()
+                    .map(ParsedField::from_named_field)
+                    .collect(),
+                field_type: FieldType::Named,
+            },
+            Fields::Unnamed(fields) => ParsedFields {
+                fields: fields
+                    .unnamed
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, field)| ParsedField::from_unnamed_field(index as u32, field))
+                    .collect(),
+                field_type: FieldType::Unnamed,
+            },
+        }
+    }
+
+    pub fn generate_parse_body(&self, self_name: TokenStream, skip: Option<&Type>) -> TokenStream {
+        let bindings = self.parsed_fields().map(|field| &field.name);
+        let parsers = self
+            .parsed_fields()
+            .map(|field| Self::generate_field_parser(field, skip));
+        let cuts = self.generate_cuts();
+        let trivia_parsers = self
+            .parsed_fields()
+            .enumerate()
+            .map(|(index, _)| match skip {
+                Some(skip) if index > 0 => quote! {
+                    __pegast_trivia.push(
+                        pegast::rules::skipping::skip_trivia::<#skip>(&mut peek_input)
+                            .map_err(|error| peek_input.state().fail(error))?,
+                    );
+                },
+                _ => quote! {},
+            });
+        let trivia_field_initializers = self.trivia_fields().map(|field| {
+            let binding = &field.name;
+
+            quote! { let #binding = __pegast_trivia.clone(); }
+        });
+        let span_bindings = self.span_fields().map(|field| &field.name);
+        let constructor = self.generate_pattern_bindings();
+
+        quote! {
+            let (new_position, result) = {
+                let mut peek_input = input.peek_only();
+                let start_position = peek_input.position();
+                #[allow(unused_mut)]
+                let mut __pegast_trivia: Vec<String> = Vec::new();
+                peek_input
+                    .state()
+                    .start_node(std::any::type_name::<Self>(), start_position);
+                #(
+                    #trivia_parsers
+                    let #bindings = #parsers
+                        .map_err(|error| peek_input.state().fail(error).cut_if(#cuts))?;
+                    let boundary = peek_input.position();
+                    peek_input.state().mark_boundary(boundary);
+                )*
+                #( #trivia_field_initializers )*
+                #(
+                    let #span_bindings =
+                        pegast::input::Span::new(start_position, peek_input.position());
+                )*
+
+                let end_position = peek_input.position();
+                peek_input.state().finish_node(end_position);
+
+                (end_position, #self_name #constructor)
+            };
+
+            input.advance_to(new_position);
+
+            Ok(result)
+        }
+    }
+
+    pub fn generate_parsed_string_body_for_structs(&self) -> TokenStream {
+        self.generate_parsed_string_body(|field| {
+            let member = &field.member;
+
+            quote! { &self.#member }
+        })
+    }
+
+    pub fn generate_parsed_string_body_for_enum_variants(&self) -> TokenStream {
+        self.generate_parsed_string_body(|field| {
+            let binding = &field.name;
+
+            quote! { #binding }
+        })
+    }
+
+    pub fn generate_parsed_string_length_body_for_structs(&self) -> TokenStream {
+        self.generate_parsed_string_length_body(|field| {
+            let member = &field.member;
+
+            quote! { &self.#member }
+        })
+    }
+
+    pub fn generate_parsed_string_length_body_for_enum_variants(&self) -> TokenStream {
+        self.generate_parsed_string_length_body(|field| {
+            let binding = &field.name;
+
+            quote! { #binding }
+        })
+    }
+
+    pub fn generate_source_slice_body_for_structs(&self) -> TokenStream {
+        self.generate_source_slice_body(|field| {
+            let member = &field.member;
+
+            quote! { &self.#member }
+        })
+    }
+
+    pub fn generate_source_slice_body_for_enum_variants(&self) -> TokenStream {
+        self.generate_source_slice_body(|field| {
+            let binding = &field.name;
+
+            quote! { #binding }
+        })
+    }
+
+    pub fn generate_visit_nodes_body_for_structs(&self) -> TokenStream {
+        self.generate_visit_nodes_body(|field| {
+            let member = &field.member;
+
+            quote! { &self.#member }
+        })
+    }
+
+    pub fn generate_visit_nodes_body_for_enum_variants(&self) -> TokenStream {
+        self.generate_visit_nodes_body(|field| {
+            let binding = &field.name;
+
+            quote! { #binding }
+        })
+    }
+
+    pub fn generate_expecting_body(&self) -> TokenStream {
+        let field_type = &self
+            .parsed_fields()
+            .next()
+            .expect("Missing first element in fields")
+            .field_type;
+
+        quote! { <#field_type as pegast::PegAstNode>::expecting() }
+    }
+
+    pub fn generate_grammar_node_body(&self, skip: Option<&Type>) -> TokenStream {
+        let nodes = self.parsed_fields().enumerate().map(|(index, field)| {
+            let field_type = &field.field_type;
+            let node = match skip {
+                Some(skip) => quote! {
+                    <#field_type as pegast::PegAstNode>::skipping_grammar_node::<#skip>(grammar)
+                },
+                _ => quote! { <#field_type as pegast::PegAstNode>::grammar_node(grammar) },
+            };
+
+            match skip {
+                Some(skip) if index > 0 => quote! {
+                    <Vec<#skip> as pegast::PegAstNode>::grammar_node(grammar),
+                    #node,
+                },
+                _ => quote! { #node, },
+            }
+        });
+
+        quote! {
+            pegast::grammar::GrammarNode::sequence(vec![ #( #nodes )* ])
+        }
+    }
+
+    pub fn generate_ignoring_pattern_bindings(&self) -> TokenStream {
+        match self.field_type {
+            FieldType::Named => quote! { { .. } },
+            FieldType::Unnamed => quote! { (..) },
+        }
+    }
+
+    pub fn generate_pattern_bindings(&self) -> TokenStream {
+        let bindings = self.fields.iter().map(|field| &field.name);
+
+        match self.field_type {
+            FieldType::Named => quote! { { #( #bindings, )* } },
+            FieldType::Unnamed => quote! { ( #( #bindings, )* ) },
+        }
+    }
+
+    fn generate_field_parser(field: &ParsedField, skip: Option<&Type>) -> TokenStream {
+        let field_type = &field.field_type;
+
+        match skip {
+            Some(skip) => quote! {
+                <#field_type as pegast::PegAstNode>
+                    ::parse_skipping::<#skip>(&mut peek_input, &mut __pegast_trivia)
+            },
+            _ => quote! { <#field_type as pegast::PegAstNode>::parse(&mut peek_input) },
+        }
+    }
+
+    fn generate_cuts(&self) -> Vec<TokenStream> {
+        let mut cuts = Vec::new();
+        let mut cut = quote! { false };
+
+        for field in self.parsed_fields() {
+            let field_type = &field.field_type;
+
+            cuts.push(cut.clone());
+
+            cut = if field.is_cut {
+                quote! { true }
+            } else {
+                quote! { #cut || <#field_type as pegast::PegAstNode>::CUT }
+            };
+        }
+
+        cuts
+    }
+
+    fn parsed_fields(&self) -> impl Iterator<Item = &ParsedField> + '_ {
+        self.fields
+            .iter()
+            .filter(|field| !field.is_span && !field.is_trivia)
+    }
+
+    fn trivia_fields(&self) -> impl Iterator<Item = &ParsedField> + '_ {
+        self.fields.iter().filter(|field| field.is_trivia)
+    }
+
+    fn span_fields(&self) -> impl Iterator<Item = &ParsedField> + '_ {
+        self.fields.iter().filter(|field| field.is_span)
+    }
+
+    fn generate_parsed_string_body(
+        &self,
+        field_accessor: impl Fn(&ParsedField) -> TokenStream,
+    ) -> TokenStream {
+        if let Some(trivia_field) = self.trivia_fields().next() {
+            let trivia = field_accessor(trivia_field);
+            let pushes = self.parsed_fields().enumerate().map(|(index, field)| {
+                let accessor = field_accessor(field);
+                let push_trivia = if index > 0 {
+                    quote! { string.extend(trivia.next().map(String::as_str)); }
+                } else {
+                    quote! {}
+                };
+
+                quote! {
+                    #push_trivia
+                    pegast::PegAstNode::push_skipped(#accessor, &mut string, &mut trivia);
+                }
+            });
+
+            quote! {
+                pegast::PegAstNode::source_slice(self).text_or_else(|| {
+                    let mut string = String::new();
+                    #[allow(unused_mut)]
+                    let mut trivia = (#trivia).iter();
+
+                    #( #pushes )*
+
+                    string
+                })
+            }
+        } else if self.parsed_fields().count() == 1 {
+            let accessor = &self
+                .parsed_fields()
+                .next()
+                .map(field_accessor)
+                .expect("Missing first element in a vector of one element");
+
+            quote! { pegast::PegAstNode::parsed_string(#accessor) }
+        } else {
+            let accessors = self.parsed_fields().map(field_accessor);
+
+            quote! {
+                pegast::PegAstNode::source_slice(self).text_or_else(|| {
+                    let mut string = String::new();
+
+                    #( string.push_str(&pegast::PegAstNode::parsed_string(#accessors)); )*
+
+                    string
+                })
+            }
+        }
+    }
+
+    fn generate_visit_nodes_body(
+        &self,
+        field_accessor: impl Fn(&ParsedField) -> TokenStream,
+    ) -> TokenStream {
+        let accessors = self.parsed_fields().map(field_accessor);
+
+        quote! {
+            #( pegast::PegAstNode::visit_nodes(#accessors, visitor); )*
+        }
+    }
+
+    fn generate_source_slice_body(
+        &self,
+        field_accessor: impl Fn(&ParsedField) -> TokenStream,
+    ) -> TokenStream {
+        if let Some(trivia_field) = self.trivia_fields().next() {
+            let trivia = field_accessor(trivia_field);
+            let joins = self.parsed_fields().enumerate().map(|(index, field)| {
+                let accessor = field_accessor(field);
+                let join_trivia = if index > 0 {
+                    quote! {
+                        slice = slice.then(trivia.next().map_or(
+                            pegast::input::SourceSlice::Empty,
+                            |trivia| pegast::input::SourceSlice::of_text(trivia),
+                        ));
+                    }
+                } else {
+                    quote! {}
+                };
+                quote! {
+                    #join_trivia
+                    slice = slice.then(
+                        pegast::PegAstNode::skipped_source_slice(#accessor, &mut trivia),
+                    );
+                }
+            });
+
+            quote! {
+                let mut slice = pegast::input::SourceSlice::Empty;
+                #[allow(unused_mut)]
+                let mut trivia = (#trivia).iter();
+
+                #( #joins )*
+
+                slice
+            }
+        } else {
+            let accessors = self.parsed_fields().map(field_accessor);
+
+            quote! {
+                pegast::input::SourceSlice::Empty
+                    #( .then(pegast::PegAstNode::source_slice(#accessors)) )*
+            }
+        }
+    }
+
+    fn generate_parsed_string_length_body(
+        &self,
+        field_accessor: impl Fn(&ParsedField) -> TokenStream,
+    ) -> TokenStream {
+        let trivia = self.trivia_fields().map(&field_accessor);
+        let accessors = self.parsed_fields().map(&field_accessor);
+
+        quote! {
+            let mut count = 0;
+
+            #( count += pegast::PegAstNode::parsed_string_length(#accessors); )*
+            #(
+                count += (#trivia).iter().map(String::len).sum::<usize>();
+            )*
+
+            count
+        }
+    }
+}
+
+enum FieldType {
+    Named,
+    Unnamed,
+}
+
+struct ParsedField {
+    name: Ident,
+    member: Member,
+    field_type: Type,
+    is_span: bool,
+    is_trivia: bool,
+    is_cut: bool,
+}
+
+impl ParsedField {
+    pub fn from_named_field(field: Field) -> Self {
+        let ident = field.ident.expect("Named field is missing the field name");
+        let attributes = ParsedAttributes::from(field.attrs);
+
+        ParsedField {
+            name: ident.clone(),
+            member: Member::Named(ident),
+            field_type: field.ty,
+            is_span: attributes.has_flag("span"),
+            is_trivia: attributes.has_flag("trivia"),
+            is_cut: attributes.has_flag("cut"),
+        }
+    }
+
+    pub fn from_unnamed_field(index: u32, field: Field) -> Self {
+        let field_type = field.ty;
+        let span = field_type.span();
+        let attributes = ParsedAttributes::from(field.attrs);
+
+        ParsedField {
+            name: Ident::new(&format!("_{}", index), span),
+            member: Member::Unnamed(Index { index, span }),
+            field_type,
+            is_span: attributes.has_flag("span"),
+            is_trivia: attributes.has_flag("trivia"),
+            is_cut: attributes.has_flag("cut"),
+        }
+    }
+}
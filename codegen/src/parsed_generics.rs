@@ -61,12 +61,17 @@ impl ParsedGenerics {
         Self::generic_list(lifetime_parameters.chain(type_parameters))
     }
 
+    // Grammar rules are keyed by `TypeId`, so generic types must be `'static`.
     pub fn where_clause(&self) -> TokenStream {
-        if self.constraints.is_empty() {
+        let mut constraints = self.constraints.clone();
+
+        if !self.lifetime_parameters.is_empty() || !self.type_parameters.is_empty() {
+            constraints.push(quote! { Self: 'static });
+        }
+
+        if constraints.is_empty() {
             quote! {}
         } else {
-            let constraints = &self.constraints;
-
             quote! { where #( #constraints ),* }
         }
     }
@@ -1,13 +1,14 @@
 use {
-    crate::{ParsedFields, ParsedGenerics, ParsedVariants},
+    crate::{ParsedAttributes, ParsedFields, ParsedGenerics, ParsedVariants},
     proc_macro2::TokenStream,
     quote::quote,
-    syn::{Data, DeriveInput, Ident},
+    syn::{Data, DeriveInput, Ident, Lit, Type},
 };
 
 pub struct ParsedType {
     name: Ident,
     generics: ParsedGenerics,
+    attributes: ParsedAttributes,
     data: TypeData,
 }
 
@@ -16,6 +17,7 @@ impl From<DeriveInput> for ParsedType {
         ParsedType {
             name: input.ident,
             generics: ParsedGenerics::from(input.generics),
+            attributes: ParsedAttributes::from(input.attrs),
             data: TypeData::from(input.data),
         }
     }
@@ -23,17 +25,21 @@ impl From<DeriveInput> for ParsedType {
 
 impl ParsedType {
     pub fn generate_peg_ast_node_impl(self) -> TokenStream {
+        let skip = self.skip_type();
+        let parse_body = self.generate_parse_body(skip.as_ref());
+        let expecting_body = self.generate_expecting_body();
         let name = self.name;
         let impl_generics = self.generics.impl_generics();
         let type_parameters = self.generics.type_parameters();
         let where_clause = self.generics.where_clause();
-        let parse_body = self.data.generate_parse_body();
         let parsed_string_body = self.data.generate_parsed_string_body();
         let parsed_string_length_body = self.data.generate_parsed_string_length_body();
-        let expecting_body = self.data.generate_expecting_body();
+        let source_slice_body = self.data.generate_source_slice_body();
+        let grammar_node_body = self.data.generate_grammar_node_body(skip.as_ref());
+        let visit_nodes_body = self.data.generate_visit_nodes_body();
 
         quote! {
-            impl #impl_generics PegAstNode for #name #type_parameters
+            impl #impl_generics pegast::PegAstNode for #name #type_parameters
             #where_clause
             {
                 fn parse(
@@ -50,10 +56,59 @@ impl ParsedType {
                     #parsed_string_length_body
                 }
 
-                fn expecting() -> Vec<String> {
+                fn source_slice(&self) -> pegast::input::SourceSlice<'_> {
+                    #source_slice_body
+                }
+
+                fn expecting() -> Vec<pegast::Expected> {
                     #expecting_body
                 }
+
+                fn grammar_node(
+                    grammar: &mut pegast::grammar::Grammar,
+                ) -> pegast::grammar::GrammarNode {
+                    grammar.rule::<Self>(|grammar| { #grammar_node_body })
+                }
+
+                fn visit_nodes<'node>(&'node self, visitor: &mut dyn FnMut(&'node dyn std::any::Any))
+                where
+                    Self: 'static,
+                {
+                    visitor(self);
+                    #visit_nodes_body
+                }
+            }
+        }
+    }
+
+    fn skip_type(&self) -> Option<Type> {
+        self.attributes.get_value("skip").map(|skip| match skip {
+            Lit::Str(skip) => skip.parse::<Type>().expect("Invalid skip type"),
+            _ => panic!("The skip attribute must be a string with the trivia type"),
+        })
+    }
+
+    fn generate_parse_body(&self, skip: Option<&Type>) -> TokenStream {
+        let parse_body = self.data.generate_parse_body(skip);
+
+        if self.attributes.has_flag("memoize") {
+            quote! {
+                pegast::rules::memo::memoized(input, |input| { #parse_body })
+            }
+        } else {
+            parse_body
+        }
+    }
+
+    fn generate_expecting_body(&self) -> TokenStream {
+        let expecting_body = self.data.generate_expecting_body();
+
+        if self.attributes.has_flag("memoize") {
+            quote! {
+                pegast::rules::memo::expecting_once::<Self>(|| { #expecting_body })
             }
+        } else {
+            expecting_body
         }
     }
 
@@ -116,6 +171,14 @@ impl ParsedType {
         let type_parameters = self.generics.type_parameters();
         let where_clause = self.generics.where_clause();
 
+        let input = if self.attributes.has_flag("memoize") {
+            quote! {
+                pegast::input::MemoizingInput::new(pegast::input::StrInput::new(string))
+            }
+        } else {
+            quote! { pegast::input::StrInput::new(string) }
+        };
+
         quote! {
             impl #impl_generics std::str::FromStr for #name #type_parameters
             #where_clause
@@ -123,9 +186,9 @@ impl ParsedType {
                 type Err = pegast::ParseError;
 
                 fn from_str(string: &str) -> Result<Self, Self::Err> {
-                    let mut input = pegast::input::ConsumingInput::new(string.chars());
+                    let mut input = #input;
 
-                    <Self as pegast::PegAstNode>::parse(&mut input)
+                    pegast::parse_complete::<Self>(&mut input)
                 }
             }
         }
@@ -153,10 +216,10 @@ impl TypeData {
         }
     }
 
-    pub fn generate_parse_body(&self) -> TokenStream {
+    pub fn generate_parse_body(&self, skip: Option<&Type>) -> TokenStream {
         match self {
-            TypeData::Enum(variants) => variants.generate_parse_body(),
-            TypeData::Struct(fields) => fields.generate_parse_body(quote! { Self }),
+            TypeData::Enum(variants) => variants.generate_parse_body(skip),
+            TypeData::Struct(fields) => fields.generate_parse_body(quote! { Self }, skip),
         }
     }
 
@@ -174,10 +237,31 @@ impl TypeData {
         }
     }
 
+    pub fn generate_source_slice_body(&self) -> TokenStream {
+        match self {
+            TypeData::Enum(variants) => variants.generate_source_slice_body(),
+            TypeData::Struct(fields) => fields.generate_source_slice_body_for_structs(),
+        }
+    }
+
+    pub fn generate_visit_nodes_body(&self) -> TokenStream {
+        match self {
+            TypeData::Enum(variants) => variants.generate_visit_nodes_body(),
+            TypeData::Struct(fields) => fields.generate_visit_nodes_body_for_structs(),
+        }
+    }
+
     pub fn generate_expecting_body(&self) -> TokenStream {
         match self {
             TypeData::Enum(variants) => variants.generate_expecting_body(),
             TypeData::Struct(fields) => fields.generate_expecting_body(),
         }
     }
+
+    pub fn generate_grammar_node_body(&self, skip: Option<&Type>) -> TokenStream {
+        match self {
+            TypeData::Enum(variants) => variants.generate_grammar_node_body(skip),
+            TypeData::Struct(fields) => fields.generate_grammar_node_body(skip),
+        }
+    }
 }
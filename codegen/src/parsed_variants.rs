@@ -2,7 +2,7 @@ use {
     crate::{ParsedAttributes, ParsedFields},
     proc_macro2::TokenStream,
     quote::quote,
-    syn::{punctuated::Punctuated, Ident, Token, Variant},
+    syn::{punctuated::Punctuated, Ident, Token, Type, Variant},
 };
 
 pub struct ParsedVariant {
@@ -68,22 +68,39 @@ impl ParsedVariants {
         })
     }
 
-    pub fn generate_parse_body(&self) -> TokenStream {
+    pub fn generate_parse_body(&self, skip: Option<&Type>) -> TokenStream {
         let variant_parsers = self.variants.iter().map(|variant| {
             let variant_name = &variant.name;
 
             variant
                 .fields
-                .generate_parse_body(quote! { Self::#variant_name })
+                .generate_parse_body(quote! { Self::#variant_name }, skip)
         });
 
+        if self.variants.is_empty() {
+            return quote! {
+                Err(pegast::ParseError::new(
+                    <Self as pegast::PegAstNode>::expecting(),
+                    input.position(),
+                ))
+            };
+        }
+
         quote! {
-            Err(())
-                #( .or_else(|_| -> Result<_, pegast::ParseError> { #variant_parsers }) )*
-                .or_else(|_| Err(pegast::ParseError {
-                    expected: Self::expecting(),
-                    position: input.position(),
-                }))
+            let checkpoint = input.state().checkpoint();
+
+            Err(pegast::ParseError::new(Vec::new(), input.position()))
+                #(
+                    .or_else(|error| -> Result<_, pegast::ParseError> {
+                        if error.cut {
+                            return Err(error);
+                        }
+
+                        input.state().rollback(checkpoint);
+
+                        #variant_parsers
+                    })
+                )*
         }
     }
 
@@ -119,6 +136,48 @@ impl ParsedVariants {
         }
     }
 
+    pub fn generate_source_slice_body(&self) -> TokenStream {
+        let variant_names = self.names();
+        let bindings = self.generate_pattern_bindings();
+        let variant_source_slices = self.variants.iter().map(|variant| {
+            variant
+                .fields
+                .generate_source_slice_body_for_enum_variants()
+        });
+
+        quote! {
+            match self {
+                #( Self::#variant_names #bindings => { #variant_source_slices } )*
+            }
+        }
+    }
+
+    pub fn generate_visit_nodes_body(&self) -> TokenStream {
+        let variant_names = self.names();
+        let bindings = self.generate_pattern_bindings();
+        let variant_visits = self
+            .variants
+            .iter()
+            .map(|variant| variant.fields.generate_visit_nodes_body_for_enum_variants());
+
+        quote! {
+            match self {
+                #( Self::#variant_names #bindings => { #variant_visits } )*
+            }
+        }
+    }
+
+    pub fn generate_grammar_node_body(&self, skip: Option<&Type>) -> TokenStream {
+        let variant_nodes = self
+            .variants
+            .iter()
+            .map(|variant| variant.fields.generate_grammar_node_body(skip));
+
+        quote! {
+            pegast::grammar::GrammarNode::choice(vec![ #( { #variant_nodes }, )* ])
+        }
+    }
+
     pub fn generate_expecting_body(&self) -> TokenStream {
         let variant_expecting = self
             .variants
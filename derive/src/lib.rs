@@ -1,21 +1,15 @@
 extern crate proc_macro;
 
-mod parsed_attributes;
-mod parsed_fields;
-mod parsed_generics;
-mod parsed_type;
-mod parsed_variants;
-
 use {
-    self::{
-        parsed_attributes::ParsedAttributes, parsed_fields::ParsedFields,
-        parsed_generics::ParsedGenerics, parsed_type::ParsedType, parsed_variants::ParsedVariants,
+    pegast_codegen::{
+        grammar::{Generator, Grammar},
+        ParsedType,
     },
     proc_macro::TokenStream,
     syn::{parse_macro_input, DeriveInput},
 };
 
-#[proc_macro_derive(PegAstNode)]
+#[proc_macro_derive(PegAstNode, attributes(pegast))]
 pub fn derive_peg_ast(input: TokenStream) -> TokenStream {
     let parsed_input = parse_macro_input!(input as DeriveInput);
     let parsed_type = ParsedType::from(parsed_input);
@@ -31,10 +25,22 @@ pub fn derive_set_entries(input: TokenStream) -> TokenStream {
     TokenStream::from(parsed_type.generate_set_entries_impl())
 }
 
-#[proc_macro_derive(FromStr)]
+#[proc_macro_derive(FromStr, attributes(pegast))]
 pub fn derive_from_str(input: TokenStream) -> TokenStream {
     let parsed_input = parse_macro_input!(input as DeriveInput);
     let parsed_type = ParsedType::from(parsed_input);
 
     TokenStream::from(parsed_type.generate_from_str_impl())
 }
+
+#[proc_macro]
+pub fn grammar(input: TokenStream) -> TokenStream {
+    let grammar = parse_macro_input!(input as Grammar);
+    let generator = Generator::new(&grammar);
+
+    if let Err(error) = generator.check_references(&grammar) {
+        return TokenStream::from(error.to_compile_error());
+    }
+
+    TokenStream::from(generator.generate(grammar))
+}
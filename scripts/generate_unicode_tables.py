@@ -0,0 +1,84 @@
+#!/usr/bin/env python3
+"""Generate src/rules/unicode/tables.rs from Python's unicodedata module."""
+
+import sys
+import unicodedata
+
+CATEGORIES = [
+    "Lu", "Ll", "Lt", "Lm", "Lo",
+    "Mn", "Mc", "Me",
+    "Nd", "Nl", "No",
+    "Pc", "Pd", "Ps", "Pe", "Pi", "Pf", "Po",
+    "Sm", "Sc", "Sk", "So",
+    "Zs", "Zl", "Zp",
+    "Cc", "Cf", "Co",
+]
+
+
+def characters():
+    for code_point in range(sys.maxunicode + 1):
+        if 0xD800 <= code_point <= 0xDFFF:
+            continue
+
+        yield chr(code_point)
+
+
+def ranges(predicate):
+    result = []
+
+    for character in characters():
+        if not predicate(character):
+            continue
+
+        code_point = ord(character)
+
+        if result and result[-1][1] == code_point - 1:
+            result[-1][1] = code_point
+        else:
+            result.append([code_point, code_point])
+
+    return result
+
+
+def write_table(output, name, table):
+    output.write(f"pub const {name}: &[(char, char)] = &[\n")
+
+    for start, end in table:
+        output.write(f"    ('\\u{{{start:x}}}', '\\u{{{end:x}}}'),\n")
+
+    output.write("];\n")
+
+
+def main():
+    output = sys.stdout
+
+    output.write(
+        "// Generated by scripts/generate_unicode_tables.py from Unicode "
+        f"{unicodedata.unidata_version}; do not edit.\n"
+    )
+
+    output.write("\n")
+    write_table(
+        output,
+        "XID_START",
+        ranges(lambda character: character != "_" and character.isidentifier()),
+    )
+
+    output.write("\n")
+    write_table(
+        output,
+        "XID_CONTINUE",
+        ranges(lambda character: ("a" + character).isidentifier()),
+    )
+
+    for category in CATEGORIES:
+        output.write("\n")
+        write_table(
+            output,
+            category.upper(),
+            ranges(lambda character: unicodedata.category(character) == category),
+        )
+
+
+if __name__ == "__main__":
+    main()
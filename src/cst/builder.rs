@@ -0,0 +1,207 @@
+use {
+    super::{CstElement, CstEvent, CstNode, CstToken, CstTrivia},
+    crate::input::{Position, Span},
+    std::mem,
+};
+
+struct Frame {
+    kind: Option<&'static str>,
+    start: Position,
+    children: Vec<CstElement>,
+}
+
+pub struct CstBuilder<'s> {
+    source: &'s str,
+    cursor: Position,
+    stack: Vec<Frame>,
+    pending_trivia: Vec<CstTrivia>,
+}
+
+impl<'s> CstBuilder<'s> {
+    pub fn new(source: &'s str) -> Self {
+        CstBuilder {
+            source,
+            cursor: Position::new(),
+            stack: Vec::new(),
+            pending_trivia: Vec::new(),
+        }
+    }
+
+    pub fn build(mut self, root_kind: &'static str, events: Vec<CstEvent>) -> CstNode {
+        self.stack.push(Frame {
+            kind: Some(root_kind),
+            start: Position::new(),
+            children: Vec::new(),
+        });
+
+        for event in events {
+            match event {
+                CstEvent::Start { kind, position } => self.start(Some(kind), position),
+                CstEvent::StartTrivia(position) => self.start(None, position),
+                CstEvent::Boundary(position) => self.flush(position),
+                CstEvent::Finish(position) => self.finish(position),
+            }
+        }
+
+        let mut end = self.cursor;
+
+        for character in self.source[end.byte_offset..].chars() {
+            end.advance(character);
+        }
+
+        while self.stack.len() > 1 {
+            self.finish(end);
+        }
+
+        self.flush(end);
+
+        let frame = self.stack.pop().expect("Missing root CST frame");
+        let mut root = CstNode {
+            kind: root_kind,
+            span: Span::new(frame.start, end),
+            children: frame.children,
+        };
+
+        let pending_trivia = mem::take(&mut self.pending_trivia);
+
+        match last_token_mut(&mut root) {
+            Some(token) => token.trailing_trivia.extend(pending_trivia),
+            None if !pending_trivia.is_empty() => root.children.push(CstElement::Token(CstToken {
+                text: String::new(),
+                span: Span::new(end, end),
+                leading_trivia: pending_trivia,
+                trailing_trivia: Vec::new(),
+            })),
+            None => {}
+        }
+
+        split_trailing_trivia(&mut root);
+
+        match root.children.as_slice() {
+            [CstElement::Node(node)] if node.kind == root_kind && node.span == root.span => {
+                match root.children.pop() {
+                    Some(CstElement::Node(node)) => node,
+                    _ => unreachable!(),
+                }
+            }
+            _ => root,
+        }
+    }
+
+    fn start(&mut self, kind: Option<&'static str>, position: Position) {
+        self.flush(position);
+        self.stack.push(Frame {
+            kind,
+            start: position.max(self.cursor),
+            children: Vec::new(),
+        });
+    }
+
+    fn finish(&mut self, position: Position) {
+        if self.stack.len() <= 1 {
+            return;
+        }
+
+        self.flush(position);
+
+        let frame = self.stack.pop().expect("Missing CST frame");
+        let span = Span::new(frame.start, self.cursor);
+
+        match frame.kind {
+            Some(kind) => self.push(CstElement::Node(CstNode {
+                kind,
+                span,
+                children: frame.children,
+            })),
+            None => {
+                for child in frame.children {
+                    let trivia = match child {
+                        CstElement::Node(node) => CstTrivia {
+                            text: node.text(),
+                            span: node.span,
+                        },
+                        CstElement::Token(token) => CstTrivia {
+                            text: token.text,
+                            span: token.span,
+                        },
+                    };
+
+                    self.pending_trivia.push(trivia);
+                }
+            }
+        }
+    }
+
+    fn flush(&mut self, position: Position) {
+        if position.byte_offset <= self.cursor.byte_offset {
+            return;
+        }
+
+        let text = self.source[self.cursor.byte_offset..position.byte_offset].to_owned();
+        let token = CstToken {
+            text,
+            span: Span::new(self.cursor, position),
+            leading_trivia: if self.in_trivia() {
+                Vec::new()
+            } else {
+                mem::take(&mut self.pending_trivia)
+            },
+            trailing_trivia: Vec::new(),
+        };
+
+        self.cursor = position;
+        self.push(CstElement::Token(token));
+    }
+
+    fn push(&mut self, element: CstElement) {
+        self.stack
+            .last_mut()
+            .expect("Missing CST frame")
+            .children
+            .push(element);
+    }
+
+    fn in_trivia(&self) -> bool {
+        self.stack.iter().any(|frame| frame.kind.is_none())
+    }
+}
+
+fn last_token_mut(node: &mut CstNode) -> Option<&mut CstToken> {
+    node.children
+        .iter_mut()
+        .rev()
+        .find_map(|child| match child {
+            CstElement::Node(node) => last_token_mut(node),
+            CstElement::Token(token) => Some(token),
+        })
+}
+
+fn collect_tokens_mut<'a>(node: &'a mut CstNode, tokens: &mut Vec<&'a mut CstToken>) {
+    for child in &mut node.children {
+        match child {
+            CstElement::Node(node) => collect_tokens_mut(node, tokens),
+            CstElement::Token(token) => tokens.push(token),
+        }
+    }
+}
+
+fn split_trailing_trivia(root: &mut CstNode) {
+    let mut tokens = Vec::new();
+
+    collect_tokens_mut(root, &mut tokens);
+
+    for index in 1..tokens.len() {
+        let (previous, next) = tokens.split_at_mut(index);
+        let previous = &mut previous[index - 1];
+        let next = &mut next[0];
+        let same_line = next
+            .leading_trivia
+            .iter()
+            .take_while(|trivia| !trivia.text.contains('\n'))
+            .count();
+
+        previous
+            .trailing_trivia
+            .extend(next.leading_trivia.drain(..same_line));
+    }
+}
@@ -0,0 +1,382 @@
+mod builder;
+
+use {
+    self::builder::CstBuilder,
+    crate::{
+        input::{Input, MemoizingInput, Position, Span, StrInput},
+        parse_complete,
+        rules::EndOfInput,
+        ParseError, PegAstNode,
+    },
+    std::{any, fmt::Write},
+};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CstEvent {
+    Start {
+        kind: &'static str,
+        position: Position,
+    },
+    StartTrivia(Position),
+    Boundary(Position),
+    Finish(Position),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SyntaxTree<T> {
+    ast: T,
+    root: CstNode,
+    source: String,
+}
+
+impl<T> SyntaxTree<T> {
+    pub fn ast(&self) -> &T {
+        &self.ast
+    }
+
+    pub fn cst(&self) -> &CstNode {
+        &self.root
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn into_parts(self) -> (T, CstNode) {
+        (self.ast, self.root)
+    }
+
+    pub fn text(&self) -> String {
+        self.root.text()
+    }
+
+    pub fn projections<'a, U>(&'a self) -> impl Iterator<Item = (&'a CstNode, &'a U)> + 'a
+    where
+        T: PegAstNode + 'static,
+        U: PegAstNode + 'static,
+    {
+        let mut projections = Vec::new();
+
+        self.ast.visit_nodes(&mut |node| {
+            if let Some(node) = node.downcast_ref::<U>() {
+                projections.push(node);
+            }
+        });
+
+        self.root
+            .descendants()
+            .filter(|node| node.is::<U>())
+            .zip(projections)
+    }
+}
+
+pub fn parse_cst<T>(source: &str) -> Result<SyntaxTree<T>, ParseError>
+where
+    T: PegAstNode,
+{
+    let mut input = MemoizingInput::new(StrInput::new(source));
+
+    input.state().enable_cst();
+
+    let ast = parse_complete::<T>(&mut input)?;
+    let events = input.state().take_cst_events();
+    let root = CstBuilder::new(source).build(any::type_name::<T>(), events);
+
+    Ok(SyntaxTree {
+        ast,
+        root,
+        source: source.to_owned(),
+    })
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CstElement {
+    Node(CstNode),
+    Token(CstToken),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CstNode {
+    kind: &'static str,
+    span: Span,
+    children: Vec<CstElement>,
+}
+
+impl CstNode {
+    pub fn kind(&self) -> &'static str {
+        self.kind
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn children(&self) -> &[CstElement] {
+        &self.children
+    }
+
+    pub fn child_nodes(&self) -> impl Iterator<Item = &CstNode> {
+        self.children.iter().filter_map(|child| match child {
+            CstElement::Node(node) => Some(node),
+            CstElement::Token(_) => None,
+        })
+    }
+
+    pub fn descendants(&self) -> Box<dyn Iterator<Item = &CstNode> + '_> {
+        Box::new(std::iter::once(self).chain(self.child_nodes().flat_map(CstNode::descendants)))
+    }
+
+    pub fn tokens(&self) -> Box<dyn Iterator<Item = &CstToken> + '_> {
+        Box::new(self.children.iter().flat_map(|child| match child {
+            CstElement::Node(node) => node.tokens(),
+            CstElement::Token(token) => Box::new(std::iter::once(token)),
+        }))
+    }
+
+    pub fn is<T>(&self) -> bool {
+        self.kind == any::type_name::<T>()
+    }
+
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+
+        for token in self.tokens() {
+            token.write_text(&mut text);
+        }
+
+        text
+    }
+
+    pub fn source_text(&self) -> String {
+        let tokens = self.tokens().collect::<Vec<_>>();
+        let last_index = tokens.len().saturating_sub(1);
+        let mut text = String::new();
+
+        for (index, token) in tokens.into_iter().enumerate() {
+            if index > 0 {
+                token
+                    .leading_trivia
+                    .iter()
+                    .for_each(|trivia| text.push_str(&trivia.text));
+            }
+
+            text.push_str(&token.text);
+
+            if index < last_index {
+                token
+                    .trailing_trivia
+                    .iter()
+                    .for_each(|trivia| text.push_str(&trivia.text));
+            }
+        }
+
+        text
+    }
+
+    pub fn project<T>(&self, source: &str) -> Result<T, ParseError>
+    where
+        T: PegAstNode,
+    {
+        let mut input = MemoizingInput::new(StrInput::new(source));
+
+        input.advance_to(self.span.start);
+
+        let node = T::parse(&mut input).map_err(|error| input.state().fail(error))?;
+        let end = input.position();
+
+        if end != self.span.end {
+            return Err(ParseError::new(EndOfInput::expecting(), end));
+        }
+
+        Ok(node)
+    }
+
+    pub fn dump(&self) -> String {
+        let mut output = String::new();
+
+        self.dump_into(&mut output, 0);
+
+        output
+    }
+
+    fn dump_into(&self, output: &mut String, depth: usize) {
+        let indentation = "  ".repeat(depth);
+
+        let _ = writeln!(output, "{}{} @ {}", indentation, self.kind, self.span);
+
+        for child in &self.children {
+            match child {
+                CstElement::Node(node) => node.dump_into(output, depth + 1),
+                CstElement::Token(token) => {
+                    let _ = writeln!(
+                        output,
+                        "{}  {:?} @ {} (leading {:?}, trailing {:?})",
+                        indentation,
+                        token.text,
+                        token.span,
+                        token
+                            .leading_trivia
+                            .iter()
+                            .map(CstTrivia::text)
+                            .collect::<Vec<_>>(),
+                        token
+                            .trailing_trivia
+                            .iter()
+                            .map(CstTrivia::text)
+                            .collect::<Vec<_>>(),
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CstToken {
+    text: String,
+    span: Span,
+    leading_trivia: Vec<CstTrivia>,
+    trailing_trivia: Vec<CstTrivia>,
+}
+
+impl CstToken {
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn leading_trivia(&self) -> &[CstTrivia] {
+        &self.leading_trivia
+    }
+
+    pub fn trailing_trivia(&self) -> &[CstTrivia] {
+        &self.trailing_trivia
+    }
+
+    fn write_text(&self, output: &mut String) {
+        for trivia in &self.leading_trivia {
+            output.push_str(&trivia.text);
+        }
+
+        output.push_str(&self.text);
+
+        for trivia in &self.trailing_trivia {
+            output.push_str(&trivia.text);
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CstTrivia {
+    text: String,
+    span: Span,
+}
+
+impl CstTrivia {
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::parse_cst,
+        crate::{
+            input::Span,
+            literals, ranges,
+            rules::{Literal, OneOrMore, Range},
+            PegAstNode,
+        },
+    };
+
+    literals!(Let: "let", Semi: ";", Space: " ");
+    ranges!(Lower: ['a', 'z']);
+
+    #[derive(Clone, Debug, pegast_derive::PegAstNode)]
+    #[pegast(skip = "Literal<Space>")]
+    struct Declaration {
+        keyword: Literal<Let>,
+        name: OneOrMore<Range<Lower>>,
+        semicolon: Literal<Semi>,
+        #[pegast(trivia)]
+        trivia: Vec<String>,
+        #[pegast(span)]
+        span: Span,
+    }
+
+    #[derive(Clone, Debug, pegast_derive::PegAstNode)]
+    #[pegast(skip = "Literal<Space>")]
+    struct Program {
+        declarations: Vec<Declaration>,
+        #[pegast(trivia)]
+        trivia: Vec<String>,
+    }
+
+    #[test]
+    fn round_trips_source_byte_for_byte() {
+        let source = "let  a;  let b ;";
+        let tree = parse_cst::<Program>(source).unwrap();
+
+        assert_eq!(tree.text(), source);
+        assert_eq!(tree.cst().source_text(), source);
+        assert_eq!(tree.ast().parsed_string(), source);
+    }
+
+    #[test]
+    fn projections_keep_absolute_positions() {
+        let source = "let  a;let b ;";
+        let tree = parse_cst::<Vec<Declaration>>(source).unwrap();
+        let projections = tree.projections::<Declaration>().collect::<Vec<_>>();
+
+        assert_eq!(projections.len(), 2);
+
+        for (node, declaration) in &projections {
+            assert_eq!(declaration.span, node.span());
+            assert_eq!(
+                declaration.parsed_string(),
+                &source[node.span().byte_range()]
+            );
+        }
+
+        let (_, second) = &projections[1];
+
+        assert_eq!((second.span.start.line, second.span.start.column), (1, 8));
+        assert_eq!((second.span.end.line, second.span.end.column), (1, 15));
+    }
+
+    #[test]
+    fn projections_borrow_nodes_from_the_parsed_tree() {
+        let tree = parse_cst::<Vec<Declaration>>("let a;let b;").unwrap();
+        let projections = tree.projections::<Declaration>().collect::<Vec<_>>();
+
+        assert_eq!(projections.len(), tree.ast().len());
+
+        for ((_, projection), declaration) in projections.iter().zip(tree.ast()) {
+            assert!(std::ptr::eq(*projection, declaration));
+        }
+    }
+
+    #[test]
+    fn emits_one_token_per_leaf_rule() {
+        let tree = parse_cst::<Declaration>("let  ab ;").unwrap();
+        let tokens = tree
+            .cst()
+            .tokens()
+            .map(|token| (token.text(), token.span().byte_range()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            tokens,
+            [("let", 0..3), ("a", 5..6), ("b", 6..7), (";", 8..9)]
+        );
+        assert_eq!(tree.text(), "let  ab ;");
+    }
+}
@@ -0,0 +1,252 @@
+use {
+    crate::ParseError,
+    std::fmt::{self, Display, Formatter},
+};
+
+const RESET: &str = "\x1b[0m";
+const BOLD_RED: &str = "\x1b[1;31m";
+const BOLD_BLUE: &str = "\x1b[1;34m";
+const BOLD: &str = "\x1b[1m";
+
+pub struct Diagnostic<'a> {
+    source: &'a str,
+    error: &'a ParseError,
+    file_name: Option<&'a str>,
+    colored: bool,
+}
+
+impl<'a> Diagnostic<'a> {
+    pub fn new(source: &'a str, error: &'a ParseError) -> Self {
+        Diagnostic {
+            source,
+            error,
+            file_name: None,
+            colored: false,
+        }
+    }
+
+    pub fn with_file_name(mut self, file_name: &'a str) -> Self {
+        self.file_name = Some(file_name);
+        self
+    }
+
+    pub fn with_colors(mut self, colored: bool) -> Self {
+        self.colored = colored;
+        self
+    }
+
+    fn paint(&self, color: &'static str) -> (&'static str, &'static str) {
+        if self.colored {
+            (color, RESET)
+        } else {
+            ("", "")
+        }
+    }
+
+    // Positions from byte inputs may fall inside a multi-byte character, so the
+    // offset is moved back to the start of that character before slicing.
+    fn offset(&self) -> usize {
+        let mut offset = self.error.position.byte_offset.min(self.source.len());
+
+        while !self.source.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        offset
+    }
+
+    fn offending_line(&self) -> (&'a str, &'a str) {
+        let offset = self.offset();
+        let line_start = self.source[..offset]
+            .rfind('\n')
+            .map(|newline| newline + 1)
+            .unwrap_or(0);
+        let line_end = self.source[offset..]
+            .find('\n')
+            .map(|newline| offset + newline)
+            .unwrap_or_else(|| self.source.len());
+        let line = &self.source[line_start..line_end];
+
+        (
+            line.strip_suffix('\r').unwrap_or(line),
+            &self.source[line_start..offset],
+        )
+    }
+
+    fn found(&self) -> String {
+        match self.source[self.offset()..].chars().next() {
+            Some(character) => format!("{:?}", character),
+            None => "end of input".to_owned(),
+        }
+    }
+}
+
+impl Display for Diagnostic<'_> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        let (error_start, error_end) = self.paint(BOLD_RED);
+        let (gutter_start, gutter_end) = self.paint(BOLD_BLUE);
+        let (bold_start, bold_end) = self.paint(BOLD);
+        let position = &self.error.position;
+        let line_number = position.line.to_string();
+        let padding = " ".repeat(line_number.len());
+        let (line, before_error) = self.offending_line();
+        let caret_indent: String = before_error
+            .chars()
+            .map(|character| if character == '\t' { '\t' } else { ' ' })
+            .collect();
+
+        writeln!(
+            formatter,
+            "{}error{}{}: unexpected {}{}",
+            error_start,
+            error_end,
+            bold_start,
+            self.found(),
+            bold_end
+        )?;
+
+        write!(formatter, "{}{}-->{} ", padding, gutter_start, gutter_end)?;
+
+        if let Some(file_name) = self.file_name {
+            write!(formatter, "{}:", file_name)?;
+        }
+
+        writeln!(formatter, "{}:{}", position.line, position.column)?;
+        writeln!(formatter, "{} {}|{}", padding, gutter_start, gutter_end)?;
+        writeln!(
+            formatter,
+            "{}{} |{} {}",
+            gutter_start, line_number, gutter_end, line
+        )?;
+        writeln!(
+            formatter,
+            "{} {}|{} {}{}^{}",
+            padding, gutter_start, gutter_end, caret_indent, error_start, error_end
+        )?;
+
+        let expected = &self.error.expected;
+
+        match expected.len() {
+            0 => Ok(()),
+            1 => writeln!(
+                formatter,
+                "{} {}={} expected {}",
+                padding, gutter_start, gutter_end, expected[0]
+            ),
+            _ => {
+                write!(
+                    formatter,
+                    "{} {}={} expected one of ",
+                    padding, gutter_start, gutter_end
+                )?;
+
+                for (index, element) in expected.iter().enumerate() {
+                    if index > 0 {
+                        write!(formatter, ", ")?;
+                    }
+
+                    write!(formatter, "{}", element)?;
+                }
+
+                writeln!(formatter)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        input::{BytesInput, StrInput},
+        literals, parse_complete,
+        rules::{AnyByte, Literal},
+        ParseError,
+    };
+
+    literals!(Hello: "hello", Newline: "\r\n");
+
+    fn parse_error(source: &str) -> ParseError {
+        parse_complete::<Literal<Hello>>(&mut StrInput::new(source)).unwrap_err()
+    }
+
+    #[test]
+    fn renders_single_line_errors() {
+        let source = "help";
+        let error = parse_error(source);
+
+        assert_eq!(
+            error.diagnostic(source).to_string(),
+            "error: unexpected 'h'\n \
+             --> 1:1\n  \
+             |\n\
+             1 | help\n  \
+             | ^\n  \
+             = expected \"hello\"\n"
+        );
+    }
+
+    #[test]
+    fn renders_errors_at_end_of_input() {
+        let source = "hello";
+        let error = parse_complete::<(Literal<Hello>, Literal<Hello>)>(&mut StrInput::new(source))
+            .unwrap_err();
+
+        assert_eq!(
+            error
+                .diagnostic(source)
+                .with_file_name("greeting.txt")
+                .to_string(),
+            "error: unexpected end of input\n \
+             --> greeting.txt:1:6\n  \
+             |\n\
+             1 | hello\n  \
+             |      ^\n  \
+             = expected \"hello\"\n"
+        );
+    }
+
+    #[test]
+    fn renders_only_the_offending_line() {
+        let source = "hello\r\nhelp\r\nhello";
+        let error = parse_complete::<(Literal<Hello>, Literal<Newline>, Literal<Hello>)>(
+            &mut StrInput::new(source),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            error.diagnostic(source).to_string(),
+            "error: unexpected 'h'\n \
+             --> 2:1\n  \
+             |\n\
+             2 | help\n  \
+             | ^\n  \
+             = expected \"hello\"\n"
+        );
+    }
+
+    #[test]
+    fn renders_colors_only_when_enabled() {
+        let source = "help";
+        let error = parse_error(source);
+        let plain = error.diagnostic(source).with_colors(false).to_string();
+        let colored = error.diagnostic(source).with_colors(true).to_string();
+
+        assert!(!plain.contains('\x1b'));
+        assert!(colored.starts_with("\x1b[1;31merror\x1b[0m\x1b[1m: unexpected 'h'\x1b[0m\n"));
+        assert!(colored.contains("\x1b[1;34m|\x1b[0m \x1b[1;31m^\x1b[0m"));
+    }
+
+    #[test]
+    fn falls_back_to_the_enclosing_character_inside_multibyte_text() {
+        let source = "éa";
+        let error =
+            parse_complete::<(AnyByte, Literal<Hello>)>(&mut BytesInput::new(source.as_bytes()))
+                .unwrap_err();
+
+        assert_eq!(error.position.byte_offset, 1);
+        assert!(error
+            .diagnostic(source)
+            .to_string()
+            .starts_with("error: unexpected 'é'\n"));
+    }
+}
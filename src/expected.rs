@@ -0,0 +1,151 @@
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Expected {
+    Literal(&'static str),
+    LiteralIgnoreCase(&'static str),
+    Keyword(&'static str),
+    Bytes(&'static [u8]),
+    CharRange(char, char),
+    CharClass(&'static [(char, char)], bool),
+    ByteRange(u8, u8),
+    AnyChar,
+    AnyByte,
+    EndOfInput,
+    Named(&'static str),
+    Not(Box<Expected>),
+    AtLeast(usize, Box<Expected>),
+}
+
+impl Display for Expected {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            Expected::Literal(literal) => write!(formatter, "{:?}", literal),
+            Expected::LiteralIgnoreCase(literal) => {
+                write!(formatter, "{:?} (ignoring case)", literal)
+            }
+            Expected::Keyword(keyword) => write!(formatter, "keyword '{}'", keyword),
+            Expected::Bytes(bytes) => {
+                write!(formatter, "b\"")?;
+
+                for &byte in bytes.iter() {
+                    write!(formatter, "{}", std::ascii::escape_default(byte))?;
+                }
+
+                write!(formatter, "\"")
+            }
+            Expected::CharRange(start, end) => {
+                write!(formatter, "a character between {:?} and {:?}", start, end)
+            }
+            Expected::CharClass(ranges, negated) => {
+                if *negated {
+                    write!(formatter, "a character not in [")?;
+                } else {
+                    write!(formatter, "a character in [")?;
+                }
+
+                for (index, &(start, end)) in ranges.iter().enumerate() {
+                    if index > 0 {
+                        write!(formatter, ", ")?;
+                    }
+
+                    if start == end {
+                        write!(formatter, "{:?}", start)?;
+                    } else {
+                        write!(formatter, "{:?}-{:?}", start, end)?;
+                    }
+                }
+
+                write!(formatter, "]")
+            }
+            Expected::ByteRange(start, end) => {
+                write!(formatter, "a byte between {:#04x} and {:#04x}", start, end)
+            }
+            Expected::AnyChar => write!(formatter, "any character"),
+            Expected::AnyByte => write!(formatter, "any byte"),
+            Expected::EndOfInput => write!(formatter, "end of input"),
+            Expected::Named(name) => write!(formatter, "{}", name),
+            Expected::Not(expected) => write!(formatter, "not {}", expected),
+            Expected::AtLeast(count, expected) => {
+                write!(formatter, "at least {} of {}", count, expected)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::Expected,
+        crate::{
+            input::{Position, StrInput},
+            literals, parse_complete,
+            rules::{Literal, Not},
+            ParseError, PegAstNode,
+        },
+    };
+
+    literals!(Yes: "yes", No: "no");
+
+    #[test]
+    fn rules_describe_what_they_expect_without_formatting() {
+        assert_eq!(
+            Option::<Literal<Yes>>::expecting(),
+            [Expected::Literal("yes")]
+        );
+        assert_eq!(
+            Not::<Literal<No>>::expecting(),
+            [Expected::Not(Box::new(Expected::Literal("no")))]
+        );
+    }
+
+    #[test]
+    fn merging_errors_deduplicates_expected_items() {
+        let position = Position::new();
+        let first = ParseError::new(
+            vec![Expected::Literal("yes"), Expected::Literal("no")],
+            position,
+        );
+        let second = ParseError::new(vec![Expected::Literal("no"), Expected::AnyChar], position);
+
+        assert_eq!(
+            first.merge(second).expected,
+            [
+                Expected::Literal("yes"),
+                Expected::Literal("no"),
+                Expected::AnyChar,
+            ]
+        );
+    }
+
+    #[test]
+    fn repeated_failures_of_one_rule_are_reported_once() {
+        let error = parse_complete::<(Option<Literal<Yes>>, Option<Literal<Yes>>, Literal<No>)>(
+            &mut StrInput::new("maybe"),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            error.expected,
+            [Expected::Literal("yes"), Expected::Literal("no")]
+        );
+    }
+
+    #[test]
+    fn formats_expected_items_only_when_displayed() {
+        assert_eq!(Expected::Literal("yes").to_string(), "\"yes\"");
+        assert_eq!(
+            Expected::CharRange('a', 'z').to_string(),
+            "a character between 'a' and 'z'"
+        );
+        assert_eq!(Expected::Bytes(b"\x00a").to_string(), "b\"\\x00a\"");
+        assert_eq!(
+            Expected::Not(Box::new(Expected::EndOfInput)).to_string(),
+            "not end of input"
+        );
+        assert_eq!(
+            Expected::AtLeast(2, Box::new(Expected::AnyChar)).to_string(),
+            "at least 2 of any character"
+        );
+    }
+}
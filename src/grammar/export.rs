@@ -0,0 +1,906 @@
+use {
+    super::{Grammar, GrammarNode, RuleId},
+    std::{
+        cell::RefCell,
+        collections::{HashMap, HashSet},
+    },
+};
+
+const CHOICE: u8 = 0;
+const EXCEPTION: u8 = 1;
+const SEQUENCE: u8 = 2;
+const UNARY: u8 = 3;
+const PRIMARY: u8 = 4;
+
+const MAX_ENUMERATED_CHARACTERS: u32 = 26;
+const MAX_EXPANDED_REPETITIONS: usize = 4;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Notation {
+    IsoEbnf,
+    W3cEbnf,
+    Peg,
+}
+
+impl Grammar {
+    pub fn export(&self, notation: Notation) -> String {
+        let exporter = Exporter::new(self, notation);
+        let mut productions = Vec::new();
+
+        if let Some(root) = self.root() {
+            if !matches!(root, GrammarNode::Rule(_)) {
+                productions.push(("Start".to_owned(), exporter.render_definition(root)));
+            }
+        }
+
+        for rule in self.rules() {
+            productions.push((
+                exporter.rule_name(rule.id),
+                exporter.render_definition(&rule.node),
+            ));
+        }
+
+        productions.extend(exporter.helpers.take());
+
+        let width = productions
+            .iter()
+            .map(|(name, _)| name.chars().count())
+            .max()
+            .unwrap_or(0);
+        let mut output = String::new();
+
+        for (name, definition) in productions {
+            let padding = " ".repeat(width - name.chars().count());
+
+            match notation {
+                Notation::IsoEbnf if definition.is_empty() => {
+                    output.push_str(&format!("{}{} = ;\n", name, padding))
+                }
+                Notation::IsoEbnf => {
+                    output.push_str(&format!("{}{} = {} ;\n", name, padding, definition))
+                }
+                Notation::W3cEbnf => {
+                    output.push_str(&format!("{}{} ::= {}\n", name, padding, definition))
+                }
+                Notation::Peg => {
+                    output.push_str(&format!("{}{} <- {}\n", name, padding, definition))
+                }
+            }
+        }
+
+        output
+    }
+}
+
+pub(super) struct Exporter {
+    notation: Notation,
+    names: HashMap<RuleId, String>,
+    helpers: RefCell<Vec<(String, String)>>,
+}
+
+impl Exporter {
+    pub(super) fn new(grammar: &Grammar, notation: Notation) -> Self {
+        let mut short_name_counts = HashMap::new();
+
+        for rule in grammar.rules() {
+            *short_name_counts.entry(short_name(rule.name)).or_insert(0) += 1;
+        }
+
+        let mut used_names = HashSet::new();
+        let names = grammar
+            .rules()
+            .iter()
+            .map(|rule| {
+                let mut name = short_name(rule.name);
+
+                if short_name_counts[&name] > 1 {
+                    name = sanitize(rule.name);
+                }
+
+                let mut unique_name = name.clone();
+                let mut suffix = 1;
+
+                while !used_names.insert(unique_name.clone()) {
+                    suffix += 1;
+                    unique_name = format!("{}_{}", name, suffix);
+                }
+
+                (rule.id, unique_name)
+            })
+            .collect();
+
+        Exporter {
+            notation,
+            names,
+            helpers: RefCell::default(),
+        }
+    }
+
+    pub(super) fn rule_name(&self, id: RuleId) -> String {
+        self.names
+            .get(&id)
+            .cloned()
+            .unwrap_or_else(|| short_name(id.name()))
+    }
+
+    fn render_definition(&self, node: &GrammarNode) -> String {
+        match simplify(node) {
+            Some(node) => self.render(&node).0,
+            None => self.render(&GrammarNode::Never).0,
+        }
+    }
+
+    fn render_at(&self, node: &GrammarNode, precedence: u8) -> String {
+        let (rendered, node_precedence) = self.render(node);
+
+        if node_precedence < precedence {
+            format!("( {} )", rendered)
+        } else {
+            rendered
+        }
+    }
+
+    fn render(&self, node: &GrammarNode) -> (String, u8) {
+        match node {
+            GrammarNode::Sequence(items) => self.render_sequence(items),
+            GrammarNode::Choice(alternatives) => {
+                let separator = match self.notation {
+                    Notation::Peg => " / ",
+                    Notation::IsoEbnf | Notation::W3cEbnf => " | ",
+                };
+                let alternatives = alternatives
+                    .iter()
+                    .map(|alternative| self.render_at(alternative, EXCEPTION))
+                    .collect::<Vec<_>>();
+
+                (alternatives.join(separator), CHOICE)
+            }
+            GrammarNode::Repeat { node, min, max } => self.render_repeat(node, *min, *max),
+            GrammarNode::Literal(literal) => self.render_literal(literal),
+            GrammarNode::LiteralIgnoreCase(literal) => self.render_literal_ignoring_case(literal),
+            GrammarNode::Bytes(bytes) => self.render_bytes(bytes),
+            GrammarNode::CharRange(start, end) => self.render_char_class(&[(*start, *end)], false),
+            GrammarNode::CharClass(ranges, negated) => self.render_char_class(ranges, *negated),
+            GrammarNode::ByteRange(start, end) => match self.notation {
+                Notation::IsoEbnf => (
+                    format!("? a byte between {:#04x} and {:#04x} ?", start, end),
+                    PRIMARY,
+                ),
+                Notation::W3cEbnf => (format!("[#x{:X}-#x{:X}]", start, end), PRIMARY),
+                Notation::Peg => (format!("[\\x{:02x}-\\x{:02x}]", start, end), PRIMARY),
+            },
+            GrammarNode::AnyChar => match self.notation {
+                Notation::IsoEbnf => ("? any character ?".to_owned(), PRIMARY),
+                Notation::W3cEbnf => ("[#x0-#x10FFFF]".to_owned(), PRIMARY),
+                Notation::Peg => (".".to_owned(), PRIMARY),
+            },
+            GrammarNode::AnyByte => match self.notation {
+                Notation::IsoEbnf => ("? any byte ?".to_owned(), PRIMARY),
+                Notation::W3cEbnf => ("[#x0-#xFF]".to_owned(), PRIMARY),
+                Notation::Peg => (".".to_owned(), PRIMARY),
+            },
+            GrammarNode::EndOfInput => match self.notation {
+                Notation::IsoEbnf => ("? end of input ?".to_owned(), PRIMARY),
+                Notation::W3cEbnf => ("/* end of input */".to_owned(), PRIMARY),
+                Notation::Peg => ("!.".to_owned(), UNARY),
+            },
+            GrammarNode::Named(name) => (self.render_prose(name), PRIMARY),
+            GrammarNode::Not(node) => match self.notation {
+                Notation::Peg => (format!("!{}", self.render_at(node, PRIMARY)), UNARY),
+                Notation::IsoEbnf | Notation::W3cEbnf => self.render_sequence(&[]),
+            },
+            GrammarNode::Peek(node) => match self.notation {
+                Notation::Peg => (format!("&{}", self.render_at(node, PRIMARY)), UNARY),
+                Notation::IsoEbnf | Notation::W3cEbnf => self.render_sequence(&[]),
+            },
+            GrammarNode::Rule(id) => (self.rule_name(*id), PRIMARY),
+            GrammarNode::Never => match self.notation {
+                Notation::IsoEbnf => ("? nothing ?".to_owned(), PRIMARY),
+                Notation::W3cEbnf => ("/* nothing */".to_owned(), PRIMARY),
+                Notation::Peg => ("!\"\"".to_owned(), UNARY),
+            },
+        }
+    }
+
+    // EBNF has no lookahead: a negative lookahead becomes an exception on the
+    // item that follows it and any other lookahead is left out, so the EBNF
+    // describes a superset of what the PEG accepts.
+    fn render_sequence(&self, items: &[GrammarNode]) -> (String, u8) {
+        let mut parts = Vec::new();
+        let mut items = items.iter().peekable();
+
+        while let Some(item) = items.next() {
+            match (item, self.notation) {
+                (_, Notation::Peg) => parts.push(self.render(item)),
+                (GrammarNode::Not(excluded), _) => {
+                    if let Some(next) = items.next_if(|next| !is_lookahead(next)) {
+                        parts.push(self.render_exception(next, excluded));
+                    }
+                }
+                (GrammarNode::Peek(_), _) => {}
+                (item, _) => parts.push(self.render(item)),
+            }
+        }
+
+        if parts.len() == 1 {
+            return parts.remove(0);
+        }
+
+        if parts.is_empty() {
+            return match self.notation {
+                Notation::IsoEbnf => (String::new(), PRIMARY),
+                Notation::W3cEbnf | Notation::Peg => ("\"\"".to_owned(), PRIMARY),
+            };
+        }
+
+        let separator = match self.notation {
+            Notation::IsoEbnf => ", ",
+            Notation::W3cEbnf | Notation::Peg => " ",
+        };
+        let parts = parts
+            .into_iter()
+            .map(|(rendered, precedence)| {
+                if precedence < SEQUENCE {
+                    format!("( {} )", rendered)
+                } else {
+                    rendered
+                }
+            })
+            .collect::<Vec<_>>();
+
+        (parts.join(separator), SEQUENCE)
+    }
+
+    fn render_exception(&self, node: &GrammarNode, excluded: &GrammarNode) -> (String, u8) {
+        let rendered = format!(
+            "{} - {}",
+            self.render_at(node, UNARY),
+            self.render_at(excluded, UNARY)
+        );
+
+        match self.notation {
+            Notation::IsoEbnf => (rendered, SEQUENCE),
+            Notation::W3cEbnf | Notation::Peg => (rendered, EXCEPTION),
+        }
+    }
+
+    fn render_repeat(&self, node: &GrammarNode, min: usize, max: Option<usize>) -> (String, u8) {
+        match self.notation {
+            Notation::IsoEbnf => {
+                let mut parts = Vec::new();
+
+                match min {
+                    0 => {}
+                    1 => parts.push(self.render_at(node, PRIMARY)),
+                    _ => parts.push(format!("{} * {}", min, self.render_at(node, PRIMARY))),
+                }
+
+                match max.map(|max| max - min) {
+                    None => parts.push(format!("{{ {} }}", self.render(node).0)),
+                    Some(0) => {}
+                    Some(1) => parts.push(format!("[ {} ]", self.render(node).0)),
+                    Some(optional) => {
+                        parts.push(format!("{} * [ {} ]", optional, self.render(node).0))
+                    }
+                }
+
+                if parts.len() == 1 && min > 1 {
+                    (parts.remove(0), UNARY)
+                } else {
+                    join_parts(parts, ", ")
+                }
+            }
+            Notation::W3cEbnf | Notation::Peg => {
+                let item = self.render_at(node, PRIMARY);
+
+                if min + max.map_or(0, |max| max - min) > MAX_EXPANDED_REPETITIONS {
+                    return self.render_counted_repeat(node, item, min, max);
+                }
+
+                let mut parts = vec![item.clone(); min];
+
+                match max.map(|max| max - min) {
+                    None => match parts.pop() {
+                        Some(last) => parts.push(format!("{}+", last)),
+                        None => parts.push(format!("{}*", item)),
+                    },
+                    Some(optional) => {
+                        parts.extend((0..optional).map(|_| format!("{}?", item)));
+                    }
+                }
+
+                join_parts(parts, " ")
+            }
+        }
+    }
+
+    // W3C EBNF and PEG have no counted repetition, so long repetitions are
+    // built from helper productions that double the count at each step.
+    fn render_counted_repeat(
+        &self,
+        node: &GrammarNode,
+        item: String,
+        min: usize,
+        max: Option<usize>,
+    ) -> (String, u8) {
+        let base = match node {
+            GrammarNode::Rule(id) => self.rule_name(*id),
+            _ => {
+                let index = self
+                    .helpers
+                    .borrow()
+                    .iter()
+                    .filter(|(name, _)| !name.contains('_'))
+                    .count()
+                    + 1;
+
+                self.helper(format!("Repeated{}", index), item)
+            }
+        };
+        let mut parts = Vec::new();
+
+        if min > 0 {
+            parts.push(self.repeat_exactly(&base, min));
+        }
+
+        match max.map(|max| max - min) {
+            None => parts.push(format!("{}*", base)),
+            Some(0) => {}
+            Some(optional) => parts.push(self.repeat_up_to(&base, optional)),
+        }
+
+        join_parts(parts, " ")
+    }
+
+    fn repeat_exactly(&self, base: &str, count: usize) -> String {
+        if count == 1 {
+            return base.to_owned();
+        }
+
+        let half = self.repeat_exactly(base, count / 2);
+        let definition = if count % 2 == 1 {
+            format!("{} {} {}", half, half, base)
+        } else {
+            format!("{} {}", half, half)
+        };
+
+        self.helper(format!("{}_times_{}", base, count), definition)
+    }
+
+    fn repeat_up_to(&self, base: &str, count: usize) -> String {
+        if count == 1 {
+            return format!("{}?", base);
+        }
+
+        let half = self.repeat_up_to(base, count / 2);
+        let definition = if count % 2 == 1 {
+            format!("{} {} {}?", half, half, base)
+        } else {
+            format!("{} {}", half, half)
+        };
+
+        self.helper(format!("{}_up_to_{}", base, count), definition)
+    }
+
+    fn helper(&self, name: String, definition: String) -> String {
+        let mut helpers = self.helpers.borrow_mut();
+
+        if let Some((existing, _)) = helpers.iter().find(|(_, existing)| *existing == definition) {
+            return existing.clone();
+        }
+
+        helpers.push((name.clone(), definition));
+        name
+    }
+
+    fn render_literal(&self, literal: &str) -> (String, u8) {
+        match self.notation {
+            Notation::IsoEbnf => join_parts(
+                quoted_runs(literal, |character| {
+                    if character.is_control() {
+                        Some(format!("? U+{:04X} ?", character as u32))
+                    } else {
+                        None
+                    }
+                }),
+                ", ",
+            ),
+            Notation::W3cEbnf => join_parts(
+                quoted_runs(literal, |character| {
+                    if character.is_control() {
+                        Some(format!("#x{:X}", character as u32))
+                    } else {
+                        None
+                    }
+                }),
+                " ",
+            ),
+            Notation::Peg => (peg_string(literal), PRIMARY),
+        }
+    }
+
+    fn render_literal_ignoring_case(&self, literal: &str) -> (String, u8) {
+        match self.notation {
+            Notation::IsoEbnf => (
+                format!("? {} ignoring case ?", peg_string(literal)),
+                PRIMARY,
+            ),
+            Notation::W3cEbnf => {
+                let mut parts = Vec::new();
+                let mut run = String::new();
+
+                for character in literal.chars() {
+                    let lower = character.to_lowercase().collect::<String>();
+                    let upper = character.to_uppercase().collect::<String>();
+
+                    if lower == upper || lower.chars().count() != 1 || upper.chars().count() != 1 {
+                        run.push(character);
+                    } else {
+                        if !run.is_empty() {
+                            parts.push(self.render_literal(&run).0);
+                            run.clear();
+                        }
+
+                        parts.push(format!("[{}{}]", upper, lower));
+                    }
+                }
+
+                if !run.is_empty() {
+                    parts.push(self.render_literal(&run).0);
+                }
+
+                join_parts(parts, " ")
+            }
+            Notation::Peg => (format!("{}i", peg_string(literal)), PRIMARY),
+        }
+    }
+
+    fn render_bytes(&self, bytes: &[u8]) -> (String, u8) {
+        match self.notation {
+            Notation::IsoEbnf => {
+                let bytes = bytes
+                    .iter()
+                    .map(|byte| format!("{:#04x}", byte))
+                    .collect::<Vec<_>>();
+
+                (format!("? bytes {} ?", bytes.join(" ")), PRIMARY)
+            }
+            Notation::W3cEbnf => join_parts(
+                bytes.iter().map(|byte| format!("#x{:X}", byte)).collect(),
+                " ",
+            ),
+            Notation::Peg => {
+                let bytes = bytes
+                    .iter()
+                    .map(|byte| format!("\\x{:02x}", byte))
+                    .collect::<String>();
+
+                (format!("\"{}\"", bytes), PRIMARY)
+            }
+        }
+    }
+
+    fn render_char_class(&self, ranges: &[(char, char)], negated: bool) -> (String, u8) {
+        match self.notation {
+            Notation::IsoEbnf => {
+                let count = ranges
+                    .iter()
+                    .map(|&(start, end)| (end as u32).saturating_sub(start as u32) + 1)
+                    .sum::<u32>();
+
+                if negated || count > MAX_ENUMERATED_CHARACTERS {
+                    (
+                        self.render_prose(&describe_char_class(ranges, negated)),
+                        PRIMARY,
+                    )
+                } else {
+                    let characters = ranges
+                        .iter()
+                        .flat_map(|&(start, end)| start..=end)
+                        .map(|character| self.render_literal(&character.to_string()).0)
+                        .collect::<Vec<_>>();
+
+                    if characters.len() == 1 {
+                        (characters.join(""), PRIMARY)
+                    } else {
+                        (characters.join(" | "), CHOICE)
+                    }
+                }
+            }
+            Notation::W3cEbnf => {
+                let ranges = ranges
+                    .iter()
+                    .map(|&(start, end)| {
+                        if start == end {
+                            w3c_class_char(start)
+                        } else {
+                            format!("{}-{}", w3c_class_char(start), w3c_class_char(end))
+                        }
+                    })
+                    .collect::<String>();
+
+                if negated {
+                    (format!("[^{}]", ranges), PRIMARY)
+                } else {
+                    (format!("[{}]", ranges), PRIMARY)
+                }
+            }
+            Notation::Peg => {
+                let ranges = ranges
+                    .iter()
+                    .map(|&(start, end)| {
+                        if start == end {
+                            peg_class_char(start)
+                        } else {
+                            format!("{}-{}", peg_class_char(start), peg_class_char(end))
+                        }
+                    })
+                    .collect::<String>();
+
+                if negated {
+                    (format!("![{}] .", ranges), SEQUENCE)
+                } else {
+                    (format!("[{}]", ranges), PRIMARY)
+                }
+            }
+        }
+    }
+
+    pub(super) fn describe(&self, node: &GrammarNode) -> String {
+        match node {
+            GrammarNode::Named(name) => (*name).to_owned(),
+            node => {
+                let exporter = Exporter {
+                    notation: Notation::Peg,
+                    names: self.names.clone(),
+                    helpers: RefCell::default(),
+                };
+
+                exporter.render(node).0
+            }
+        }
+    }
+
+    fn render_prose(&self, text: &str) -> String {
+        match self.notation {
+            Notation::IsoEbnf => format!("? {} ?", text.replace('?', "")),
+            Notation::W3cEbnf => format!("/* {} */", text.replace("*/", "* /")),
+            Notation::Peg => format!("<{}>", text.replace('>', "")),
+        }
+    }
+}
+
+pub(super) fn simplify(node: &GrammarNode) -> Option<GrammarNode> {
+    match node {
+        GrammarNode::Sequence(items) => {
+            let mut simplified = Vec::new();
+
+            for item in items {
+                match simplify(item)? {
+                    GrammarNode::Sequence(nested) => simplified.extend(nested),
+                    item => simplified.push(item),
+                }
+            }
+
+            Some(GrammarNode::sequence(simplified))
+        }
+        GrammarNode::Choice(alternatives) => {
+            let mut simplified = Vec::new();
+
+            for alternative in alternatives.iter().filter_map(simplify) {
+                match alternative {
+                    GrammarNode::Choice(nested) => simplified.extend(nested),
+                    alternative => simplified.push(alternative),
+                }
+            }
+
+            if simplified.is_empty() {
+                None
+            } else {
+                Some(GrammarNode::choice(simplified))
+            }
+        }
+        GrammarNode::Repeat { node, min, max } => match (simplify(node), max) {
+            (_, Some(0)) => Some(GrammarNode::empty()),
+            (None, _) if *min == 0 => Some(GrammarNode::empty()),
+            (None, _) => None,
+            (Some(GrammarNode::Sequence(items)), _) if items.is_empty() => {
+                Some(GrammarNode::empty())
+            }
+            (Some(node), _) => Some(GrammarNode::repeat(node, *min, *max)),
+        },
+        GrammarNode::Not(node) => match simplify(node) {
+            Some(GrammarNode::Sequence(items)) if items.is_empty() => None,
+            Some(node) => Some(GrammarNode::not_followed_by(node)),
+            None => Some(GrammarNode::empty()),
+        },
+        GrammarNode::Peek(node) => simplify(node).map(GrammarNode::followed_by),
+        GrammarNode::Never => None,
+        GrammarNode::Literal("") | GrammarNode::LiteralIgnoreCase("") | GrammarNode::Bytes(&[]) => {
+            Some(GrammarNode::empty())
+        }
+        node => Some(node.clone()),
+    }
+}
+
+fn is_lookahead(node: &GrammarNode) -> bool {
+    matches!(node, GrammarNode::Not(_) | GrammarNode::Peek(_))
+}
+
+fn join_parts(mut parts: Vec<String>, separator: &str) -> (String, u8) {
+    if parts.len() == 1 {
+        (parts.remove(0), PRIMARY)
+    } else {
+        (parts.join(separator), SEQUENCE)
+    }
+}
+
+fn quoted_runs(text: &str, escape: impl Fn(char) -> Option<String>) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut run = String::new();
+
+    for character in text.chars() {
+        if let Some(escaped) = escape(character) {
+            flush_quoted_run(&mut run, &mut parts);
+            parts.push(escaped);
+            continue;
+        }
+
+        let conflicts =
+            (character == '"' && run.contains('\'')) || (character == '\'' && run.contains('"'));
+
+        if conflicts {
+            flush_quoted_run(&mut run, &mut parts);
+        }
+
+        run.push(character);
+    }
+
+    flush_quoted_run(&mut run, &mut parts);
+
+    if parts.is_empty() {
+        parts.push("\"\"".to_owned());
+    }
+
+    parts
+}
+
+fn flush_quoted_run(run: &mut String, parts: &mut Vec<String>) {
+    if run.is_empty() {
+        return;
+    }
+
+    if run.contains('"') {
+        parts.push(format!("'{}'", run));
+    } else {
+        parts.push(format!("\"{}\"", run));
+    }
+
+    run.clear();
+}
+
+pub(super) fn peg_string(text: &str) -> String {
+    let mut string = String::from("\"");
+
+    for character in text.chars() {
+        match character {
+            '"' => string.push_str("\\\""),
+            '\\' => string.push_str("\\\\"),
+            '\n' => string.push_str("\\n"),
+            '\r' => string.push_str("\\r"),
+            '\t' => string.push_str("\\t"),
+            character if character.is_control() => {
+                string.push_str(&format!("\\u{{{:x}}}", character as u32))
+            }
+            character => string.push(character),
+        }
+    }
+
+    string.push('"');
+    string
+}
+
+fn peg_class_char(character: char) -> String {
+    match character {
+        ']' | '[' | '\\' | '-' | '^' => format!("\\{}", character),
+        '\n' => "\\n".to_owned(),
+        '\r' => "\\r".to_owned(),
+        '\t' => "\\t".to_owned(),
+        character if character.is_control() => format!("\\u{{{:x}}}", character as u32),
+        character => character.to_string(),
+    }
+}
+
+fn w3c_class_char(character: char) -> String {
+    if character.is_ascii_alphanumeric() {
+        character.to_string()
+    } else {
+        format!("#x{:X}", character as u32)
+    }
+}
+
+fn describe_char_class(ranges: &[(char, char)], negated: bool) -> String {
+    let ranges = ranges
+        .iter()
+        .map(|&(start, end)| {
+            if start == end {
+                format!("{:?}", start)
+            } else {
+                format!("{:?} to {:?}", start, end)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    if negated {
+        format!("any character except {}", ranges)
+    } else {
+        format!("a character in {}", ranges)
+    }
+}
+
+fn short_name(type_name: &str) -> String {
+    let mut name = String::new();
+    let mut segment = String::new();
+    let mut characters = type_name.chars().peekable();
+
+    while let Some(character) = characters.next() {
+        if character.is_alphanumeric() || character == '_' {
+            segment.push(character);
+        } else if character == ':' && characters.peek() == Some(&':') {
+            characters.next();
+            segment.clear();
+        } else {
+            name.push_str(&segment);
+            segment.clear();
+
+            if !name.is_empty() && !name.ends_with('_') {
+                name.push('_');
+            }
+        }
+    }
+
+    name.push_str(&segment);
+    name.trim_end_matches('_').to_owned()
+}
+
+fn sanitize(type_name: &str) -> String {
+    let mut name = String::new();
+
+    for character in type_name.chars() {
+        if character.is_alphanumeric() || character == '_' {
+            name.push(character);
+        } else if !name.is_empty() && !name.ends_with('_') {
+            name.push('_');
+        }
+    }
+
+    name.trim_end_matches('_').to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::Notation,
+        crate::{
+            grammar::Grammar,
+            literals, ranges,
+            rules::{Any, Delimitted, Literal, Not, OneOrMore, Peek, Range, Repeat},
+            PegAstNode,
+        },
+    };
+
+    literals!(A: "a", Comma: ",", Quote: "\"", Empty: "");
+    ranges!(Digit: ['0', '9']);
+
+    fn assert_exports<T: PegAstNode>(iso: &str, w3c: &str, peg: &str) {
+        let grammar = Grammar::of::<T>();
+
+        assert_eq!(grammar.export(Notation::IsoEbnf), iso);
+        assert_eq!(grammar.export(Notation::W3cEbnf), w3c);
+        assert_eq!(grammar.export(Notation::Peg), peg);
+    }
+
+    #[test]
+    fn exports_literals_and_ranges() {
+        assert_exports::<Literal<A>>("Start = \"a\" ;\n", "Start ::= \"a\"\n", "Start <- \"a\"\n");
+        assert_exports::<Literal<Quote>>(
+            "Start = '\"' ;\n",
+            "Start ::= '\"'\n",
+            "Start <- \"\\\"\"\n",
+        );
+        assert_exports::<Range<Digit>>(
+            "Start = \"0\" | \"1\" | \"2\" | \"3\" | \"4\" | \"5\" | \"6\" | \"7\" | \"8\" | \"9\" ;\n",
+            "Start ::= [0-9]\n",
+            "Start <- [0-9]\n",
+        );
+    }
+
+    #[test]
+    fn exports_repetitions() {
+        assert_exports::<Option<Literal<A>>>(
+            "Start = [ \"a\" ] ;\n",
+            "Start ::= \"a\"?\n",
+            "Start <- \"a\"?\n",
+        );
+        assert_exports::<Vec<Literal<A>>>(
+            "Start = { \"a\" } ;\n",
+            "Start ::= \"a\"*\n",
+            "Start <- \"a\"*\n",
+        );
+        assert_exports::<OneOrMore<Literal<A>>>(
+            "Start = \"a\", { \"a\" } ;\n",
+            "Start ::= \"a\"+\n",
+            "Start <- \"a\"+\n",
+        );
+        assert_exports::<Delimitted<Literal<A>, Literal<Comma>>>(
+            "Start = \"a\", { \",\", \"a\" } ;\n",
+            "Start ::= \"a\" ( \",\" \"a\" )*\n",
+            "Start <- \"a\" ( \",\" \"a\" )*\n",
+        );
+    }
+
+    #[test]
+    fn exports_lookaheads() {
+        assert_exports::<(Not<Literal<A>>, Any)>(
+            "Start = ? any character ? - \"a\" ;\n",
+            "Start ::= [#x0-#x10FFFF] - \"a\"\n",
+            "Start <- !\"a\" .\n",
+        );
+        assert_exports::<(Literal<A>, Peek<Literal<Comma>>)>(
+            "Start = \"a\" ;\n",
+            "Start ::= \"a\"\n",
+            "Start <- \"a\" &\",\"\n",
+        );
+        assert_exports::<(Literal<Comma>, Not<Literal<A>>)>(
+            "Start = \",\" ;\n",
+            "Start ::= \",\"\n",
+            "Start <- \",\" !\"a\"\n",
+        );
+    }
+
+    #[test]
+    fn exports_empty_sequences_without_empty_terminals() {
+        assert_exports::<(Literal<Empty>, Option<Literal<A>>)>(
+            "Start = [ \"a\" ] ;\n",
+            "Start ::= \"a\"?\n",
+            "Start <- \"a\"?\n",
+        );
+        assert_exports::<Literal<Empty>>("Start = ;\n", "Start ::= \"\"\n", "Start <- \"\"\n");
+    }
+
+    #[test]
+    fn exports_long_repetitions_without_expanding_them() {
+        assert_exports::<(Repeat<Literal<A>, 0, 1000>, [Literal<Comma>; 5])>(
+            "Start = 1000 * [ \"a\" ], 5 * \",\" ;\n",
+            "Start                ::= Repeated1_up_to_1000 Repeated2_times_5\n\
+             Repeated1            ::= \"a\"\n\
+             Repeated1_up_to_3    ::= Repeated1? Repeated1? Repeated1?\n\
+             Repeated1_up_to_7    ::= Repeated1_up_to_3 Repeated1_up_to_3 Repeated1?\n\
+             Repeated1_up_to_15   ::= Repeated1_up_to_7 Repeated1_up_to_7 Repeated1?\n\
+             Repeated1_up_to_31   ::= Repeated1_up_to_15 Repeated1_up_to_15 Repeated1?\n\
+             Repeated1_up_to_62   ::= Repeated1_up_to_31 Repeated1_up_to_31\n\
+             Repeated1_up_to_125  ::= Repeated1_up_to_62 Repeated1_up_to_62 Repeated1?\n\
+             Repeated1_up_to_250  ::= Repeated1_up_to_125 Repeated1_up_to_125\n\
+             Repeated1_up_to_500  ::= Repeated1_up_to_250 Repeated1_up_to_250\n\
+             Repeated1_up_to_1000 ::= Repeated1_up_to_500 Repeated1_up_to_500\n\
+             Repeated2            ::= \",\"\n\
+             Repeated2_times_2    ::= Repeated2 Repeated2\n\
+             Repeated2_times_5    ::= Repeated2_times_2 Repeated2_times_2 Repeated2\n",
+            "Start                <- Repeated1_up_to_1000 Repeated2_times_5\n\
+             Repeated1            <- \"a\"\n\
+             Repeated1_up_to_3    <- Repeated1? Repeated1? Repeated1?\n\
+             Repeated1_up_to_7    <- Repeated1_up_to_3 Repeated1_up_to_3 Repeated1?\n\
+             Repeated1_up_to_15   <- Repeated1_up_to_7 Repeated1_up_to_7 Repeated1?\n\
+             Repeated1_up_to_31   <- Repeated1_up_to_15 Repeated1_up_to_15 Repeated1?\n\
+             Repeated1_up_to_62   <- Repeated1_up_to_31 Repeated1_up_to_31\n\
+             Repeated1_up_to_125  <- Repeated1_up_to_62 Repeated1_up_to_62 Repeated1?\n\
+             Repeated1_up_to_250  <- Repeated1_up_to_125 Repeated1_up_to_125\n\
+             Repeated1_up_to_500  <- Repeated1_up_to_250 Repeated1_up_to_250\n\
+             Repeated1_up_to_1000 <- Repeated1_up_to_500 Repeated1_up_to_500\n\
+             Repeated2            <- \",\"\n\
+             Repeated2_times_2    <- Repeated2 Repeated2\n\
+             Repeated2_times_5    <- Repeated2_times_2 Repeated2_times_2 Repeated2\n",
+        );
+    }
+}
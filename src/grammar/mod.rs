@@ -0,0 +1,273 @@
+mod export;
+mod railroad;
+
+pub use self::{export::Notation, railroad::Railroad};
+use {
+    crate::PegAstNode,
+    std::{
+        any::{type_name, TypeId},
+        collections::HashMap,
+        hash::{Hash, Hasher},
+    },
+};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GrammarNode {
+    Sequence(Vec<GrammarNode>),
+    Choice(Vec<GrammarNode>),
+    Repeat {
+        node: Box<GrammarNode>,
+        min: usize,
+        max: Option<usize>,
+    },
+    Literal(&'static str),
+    LiteralIgnoreCase(&'static str),
+    Bytes(&'static [u8]),
+    CharRange(char, char),
+    CharClass(&'static [(char, char)], bool),
+    ByteRange(u8, u8),
+    AnyChar,
+    AnyByte,
+    EndOfInput,
+    Named(&'static str),
+    Not(Box<GrammarNode>),
+    Peek(Box<GrammarNode>),
+    Rule(RuleId),
+    Never,
+}
+
+// Rules are identified by the `TypeId` of the node type. The type name is only
+// kept for display, since it is not guaranteed to be unique.
+#[derive(Clone, Copy, Debug)]
+pub struct RuleId {
+    type_id: TypeId,
+    name: &'static str,
+}
+
+impl RuleId {
+    pub fn of<T>() -> Self
+    where
+        T: ?Sized + 'static,
+    {
+        RuleId {
+            type_id: TypeId::of::<T>(),
+            name: type_name::<T>(),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl PartialEq for RuleId {
+    fn eq(&self, other: &Self) -> bool {
+        self.type_id == other.type_id
+    }
+}
+
+impl Eq for RuleId {}
+
+impl Hash for RuleId {
+    fn hash<H: Hasher>(&self, hasher: &mut H) {
+        self.type_id.hash(hasher);
+    }
+}
+
+impl GrammarNode {
+    pub fn empty() -> Self {
+        GrammarNode::Sequence(Vec::new())
+    }
+
+    pub fn sequence(mut nodes: Vec<GrammarNode>) -> Self {
+        if nodes.len() == 1 {
+            nodes.remove(0)
+        } else {
+            GrammarNode::Sequence(nodes)
+        }
+    }
+
+    pub fn choice(mut nodes: Vec<GrammarNode>) -> Self {
+        if nodes.len() == 1 {
+            nodes.remove(0)
+        } else {
+            GrammarNode::Choice(nodes)
+        }
+    }
+
+    pub fn repeat(node: GrammarNode, min: usize, max: Option<usize>) -> Self {
+        GrammarNode::Repeat {
+            node: Box::new(node),
+            min,
+            max,
+        }
+    }
+
+    pub fn optional(node: GrammarNode) -> Self {
+        GrammarNode::repeat(node, 0, Some(1))
+    }
+
+    pub fn zero_or_more(node: GrammarNode) -> Self {
+        GrammarNode::repeat(node, 0, None)
+    }
+
+    pub fn one_or_more(node: GrammarNode) -> Self {
+        GrammarNode::repeat(node, 1, None)
+    }
+
+    pub fn not_followed_by(node: GrammarNode) -> Self {
+        GrammarNode::Not(Box::new(node))
+    }
+
+    pub fn followed_by(node: GrammarNode) -> Self {
+        GrammarNode::Peek(Box::new(node))
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GrammarRule {
+    pub id: RuleId,
+    pub name: &'static str,
+    pub node: GrammarNode,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Grammar {
+    root: Option<GrammarNode>,
+    rules: Vec<GrammarRule>,
+    indices: HashMap<RuleId, usize>,
+}
+
+impl Grammar {
+    pub fn new() -> Self {
+        Grammar::default()
+    }
+
+    pub fn of<T>() -> Self
+    where
+        T: PegAstNode,
+    {
+        let mut grammar = Grammar::new();
+        let root = T::grammar_node(&mut grammar);
+
+        grammar.root = Some(root);
+        grammar
+    }
+
+    pub fn rule<T>(&mut self, describe: impl FnOnce(&mut Grammar) -> GrammarNode) -> GrammarNode
+    where
+        T: ?Sized + 'static,
+    {
+        let id = RuleId::of::<T>();
+
+        if !self.indices.contains_key(&id) {
+            let index = self.rules.len();
+
+            self.indices.insert(id, index);
+            self.rules.push(GrammarRule {
+                id,
+                name: id.name(),
+                node: GrammarNode::empty(),
+            });
+
+            self.rules[index].node = describe(self);
+        }
+
+        GrammarNode::Rule(id)
+    }
+
+    pub fn root(&self) -> Option<&GrammarNode> {
+        self.root.as_ref()
+    }
+
+    pub fn rules(&self) -> &[GrammarRule] {
+        &self.rules
+    }
+
+    pub fn get(&self, id: RuleId) -> Option<&GrammarNode> {
+        self.indices.get(&id).map(|&index| &self.rules[index].node)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{Grammar, GrammarNode, RuleId},
+        crate::{literals, rules::Literal, PegAstNode},
+    };
+
+    literals!(Open: "(", Close: ")", Atom: "x", Comma: ",");
+
+    #[derive(Clone, Debug, pegast_derive::PegAstNode)]
+    struct List {
+        open: Literal<Open>,
+        items: Vec<Item>,
+        close: Literal<Close>,
+    }
+
+    #[derive(Clone, Debug, pegast_derive::PegAstNode)]
+    enum Item {
+        Atom(Literal<Atom>),
+        List(Box<List>),
+    }
+
+    #[derive(Clone, Debug, pegast_derive::PegAstNode)]
+    struct Pair<T: PegAstNode> {
+        left: T,
+        separator: Literal<Comma>,
+        right: T,
+    }
+
+    #[test]
+    fn describes_mutually_recursive_types_once() {
+        let grammar = Grammar::of::<List>();
+        let list = RuleId::of::<List>();
+        let item = RuleId::of::<Item>();
+
+        assert_eq!(grammar.root(), Some(&GrammarNode::Rule(list)));
+        assert_eq!(grammar.rules().len(), 2);
+        assert_eq!(grammar.rules()[0].id, list);
+        assert_eq!(grammar.rules()[1].id, item);
+        assert_eq!(
+            grammar.get(list),
+            Some(&GrammarNode::Sequence(vec![
+                GrammarNode::Literal("("),
+                GrammarNode::zero_or_more(GrammarNode::Rule(item)),
+                GrammarNode::Literal(")"),
+            ]))
+        );
+        assert_eq!(
+            grammar.get(item),
+            Some(&GrammarNode::Choice(vec![
+                GrammarNode::Literal("x"),
+                GrammarNode::Rule(list),
+            ]))
+        );
+    }
+
+    #[test]
+    fn keeps_separate_rules_for_each_generic_instantiation() {
+        let grammar = Grammar::of::<(Pair<Literal<Atom>>, Pair<List>)>();
+        let atoms = RuleId::of::<Pair<Literal<Atom>>>();
+        let lists = RuleId::of::<Pair<List>>();
+
+        assert_ne!(atoms, lists);
+        assert_eq!(
+            grammar.get(atoms),
+            Some(&GrammarNode::Sequence(vec![
+                GrammarNode::Literal("x"),
+                GrammarNode::Literal(","),
+                GrammarNode::Literal("x"),
+            ]))
+        );
+        assert_eq!(
+            grammar.get(lists),
+            Some(&GrammarNode::Sequence(vec![
+                GrammarNode::Rule(RuleId::of::<List>()),
+                GrammarNode::Literal(","),
+                GrammarNode::Rule(RuleId::of::<List>()),
+            ]))
+        );
+        assert_eq!(grammar.get(RuleId::of::<Pair<Item>>()), None);
+    }
+}
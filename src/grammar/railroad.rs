@@ -0,0 +1,617 @@
+use {
+    super::{
+        export::{peg_string, simplify, Exporter},
+        Grammar, GrammarNode, Notation,
+    },
+    std::fmt::Write as _,
+};
+
+const ARC_RADIUS: i32 = 10;
+const VERTICAL_SEPARATION: i32 = 8;
+const HORIZONTAL_SEPARATION: i32 = 10;
+const BOX_HEIGHT: i32 = 22;
+const BOX_PADDING: i32 = 10;
+const CHARACTER_WIDTH: i32 = 9;
+const TEXT_BASELINE_OFFSET: i32 = 5;
+const LABEL_CHARACTER_WIDTH: i32 = 7;
+const MARGIN: i32 = 20;
+const END_MARKER_WIDTH: i32 = 10;
+
+const STYLE: &str = "\
+svg.railroad { background-color: #fff; }
+svg.railroad path { stroke-width: 2; stroke: #333; fill: none; }
+svg.railroad rect { stroke-width: 2; stroke: #333; fill: #f4f4ff; }
+svg.railroad rect.terminal { fill: #f0fff0; }
+svg.railroad rect.class { fill: #fff8e8; }
+svg.railroad rect.lookahead { fill: #fff; stroke-dasharray: 4 3; }
+svg.railroad text { font-family: monospace; font-size: 14px; text-anchor: middle; }
+svg.railroad text.label { font-family: sans-serif; font-size: 12px; font-style: italic; }
+svg.railroad a text { fill: #1a4fa0; text-decoration: underline; }";
+
+pub struct Railroad<'a> {
+    grammar: &'a Grammar,
+    exporter: Exporter,
+    link_prefix: &'a str,
+    link_suffix: &'a str,
+}
+
+impl<'a> Railroad<'a> {
+    pub fn new(grammar: &'a Grammar) -> Self {
+        Railroad {
+            grammar,
+            exporter: Exporter::new(grammar, Notation::Peg),
+            link_prefix: "#",
+            link_suffix: "",
+        }
+    }
+
+    pub fn with_links(mut self, prefix: &'a str, suffix: &'a str) -> Self {
+        self.link_prefix = prefix;
+        self.link_suffix = suffix;
+        self
+    }
+
+    pub fn rule_names(&self) -> Vec<String> {
+        self.productions().map(|(name, _)| name).collect()
+    }
+
+    pub fn rule_svg(&self, name: &str) -> Option<String> {
+        self.productions()
+            .find(|(rule_name, _)| rule_name == name)
+            .map(|(_, node)| self.svg(node))
+    }
+
+    pub fn svgs(&self) -> Vec<(String, String)> {
+        self.productions()
+            .map(|(name, node)| (name, self.svg(node)))
+            .collect()
+    }
+
+    pub fn html(&self) -> String {
+        let mut html = String::new();
+
+        html.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+        html.push_str("<title>Grammar</title>\n<style>\n");
+        html.push_str("body { font-family: sans-serif; margin: 2em; }\n");
+        html.push_str("section { margin-bottom: 2em; }\n");
+        html.push_str("h2 { font-family: monospace; font-size: 1.1em; }\n");
+        html.push_str("h2 a { color: inherit; text-decoration: none; }\n");
+        html.push_str("</style>\n</head>\n<body>\n");
+
+        for (name, svg) in self.svgs() {
+            let name = escape(&name);
+
+            let _ = writeln!(
+                html,
+                "<section id=\"{}\">\n<h2><a href=\"#{}\">{}</a></h2>\n{}</section>",
+                name, name, name, svg
+            );
+        }
+
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+
+    fn productions(&self) -> impl Iterator<Item = (String, &GrammarNode)> + '_ {
+        let start = self
+            .grammar
+            .root()
+            .filter(|root| !matches!(root, GrammarNode::Rule(_)))
+            .map(|root| ("Start".to_owned(), root));
+        let rules = self
+            .grammar
+            .rules()
+            .iter()
+            .map(move |rule| (self.exporter.rule_name(rule.id), &rule.node));
+
+        start.into_iter().chain(rules)
+    }
+
+    fn svg(&self, node: &GrammarNode) -> String {
+        let diagram = match simplify(node) {
+            Some(node) => self.element(&node),
+            None => self.element(&GrammarNode::Never),
+        };
+        let width = diagram.width + 2 * (MARGIN + END_MARKER_WIDTH);
+        let height = diagram.up + diagram.down + 2 * MARGIN;
+        let x = MARGIN;
+        let y = MARGIN + diagram.up;
+        let end = x + END_MARKER_WIDTH + diagram.width;
+        let mut svg = String::new();
+
+        let _ = writeln!(
+            svg,
+            "<svg class=\"railroad\" xmlns=\"http://www.w3.org/2000/svg\" \
+             xmlns:xlink=\"http://www.w3.org/1999/xlink\" \
+             width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">",
+            width, height, width, height
+        );
+        let _ = writeln!(svg, "<style>\n{}\n</style>", STYLE);
+        let _ = writeln!(
+            svg,
+            "<path d=\"M {} {} v {} M {} {} h {}\"/>",
+            x,
+            y - BOX_HEIGHT / 2,
+            BOX_HEIGHT,
+            x,
+            y,
+            END_MARKER_WIDTH
+        );
+
+        diagram.draw(x + END_MARKER_WIDTH, y, &mut svg);
+
+        let _ = writeln!(
+            svg,
+            "<path d=\"M {} {} h {} M {} {} v {}\"/>",
+            end,
+            y,
+            END_MARKER_WIDTH,
+            end + END_MARKER_WIDTH,
+            y - BOX_HEIGHT / 2,
+            BOX_HEIGHT
+        );
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    fn element(&self, node: &GrammarNode) -> Element {
+        match node {
+            GrammarNode::Sequence(items) => {
+                Element::sequence(items.iter().map(|item| self.element(item)).collect())
+            }
+            GrammarNode::Choice(alternatives) => Element::choice(
+                alternatives
+                    .iter()
+                    .map(|alternative| self.element(alternative))
+                    .collect(),
+            ),
+            GrammarNode::Repeat { node, min, max } => {
+                let item = self.element(node);
+
+                match (min, max) {
+                    (0, Some(1)) => Element::optional(item),
+                    (0, None) => Element::optional(Element::repeated(item, Element::skip())),
+                    (1, None) => Element::repeated(item, Element::skip()),
+                    (&min, &max) => {
+                        let label = match max {
+                            Some(max) if max == min => format!("{} times", min),
+                            Some(max) => format!("{} to {} times", min, max),
+                            None => format!("at least {} times", min),
+                        };
+                        let repeated = Element::repeated(item, Element::label(label));
+
+                        if min == 0 {
+                            Element::optional(repeated)
+                        } else {
+                            repeated
+                        }
+                    }
+                }
+            }
+            GrammarNode::Literal(literal) => Element::boxed(peg_string(literal), "terminal", None),
+            GrammarNode::LiteralIgnoreCase(literal) => Element::boxed(
+                format!("{} (any case)", peg_string(literal)),
+                "terminal",
+                None,
+            ),
+            GrammarNode::Not(node) => Element::boxed(
+                format!("not followed by {}", self.exporter.describe(node)),
+                "lookahead",
+                None,
+            ),
+            GrammarNode::Peek(node) => Element::boxed(
+                format!("followed by {}", self.exporter.describe(node)),
+                "lookahead",
+                None,
+            ),
+            GrammarNode::EndOfInput => Element::boxed("end of input".to_owned(), "class", None),
+            GrammarNode::Never => Element::boxed("nothing".to_owned(), "lookahead", None),
+            GrammarNode::Rule(id) => {
+                let name = self.exporter.rule_name(*id);
+                let link = format!("{}{}{}", self.link_prefix, name, self.link_suffix);
+
+                Element::boxed(name, "nonterminal", Some(link))
+            }
+            node => Element::boxed(self.exporter.describe(node), "class", None),
+        }
+    }
+}
+
+struct Element {
+    kind: ElementKind,
+    width: i32,
+    up: i32,
+    down: i32,
+}
+
+enum ElementKind {
+    Skip,
+    Box {
+        text: String,
+        class: &'static str,
+        link: Option<String>,
+    },
+    Label(String),
+    Sequence(Vec<Element>),
+    Choice(Vec<(Element, i32)>),
+    Repeated(Box<Element>, Box<Element>, i32),
+}
+
+impl Element {
+    fn skip() -> Self {
+        Element {
+            kind: ElementKind::Skip,
+            width: 0,
+            up: 0,
+            down: 0,
+        }
+    }
+
+    fn boxed(text: String, class: &'static str, link: Option<String>) -> Self {
+        Element {
+            width: text.chars().count() as i32 * CHARACTER_WIDTH + 2 * BOX_PADDING,
+            up: BOX_HEIGHT / 2,
+            down: BOX_HEIGHT / 2,
+            kind: ElementKind::Box { text, class, link },
+        }
+    }
+
+    fn label(text: String) -> Self {
+        Element {
+            width: text.chars().count() as i32 * LABEL_CHARACTER_WIDTH + 2 * BOX_PADDING,
+            up: BOX_HEIGHT / 2,
+            down: BOX_HEIGHT / 2,
+            kind: ElementKind::Label(text),
+        }
+    }
+
+    fn sequence(mut items: Vec<Element>) -> Self {
+        items.retain(|item| !matches!(item.kind, ElementKind::Skip));
+
+        match items.len() {
+            0 => Element::skip(),
+            1 => items.remove(0),
+            _ => Element {
+                width: items.iter().map(|item| item.width).sum::<i32>()
+                    + HORIZONTAL_SEPARATION * (items.len() as i32 - 1),
+                up: items.iter().map(|item| item.up).max().unwrap_or(0),
+                down: items.iter().map(|item| item.down).max().unwrap_or(0),
+                kind: ElementKind::Sequence(items),
+            },
+        }
+    }
+
+    fn choice(mut alternatives: Vec<Element>) -> Self {
+        if alternatives.len() == 1 {
+            return alternatives.remove(0);
+        }
+
+        let width = alternatives
+            .iter()
+            .map(|alternative| alternative.width)
+            .max()
+            .unwrap_or(0)
+            + 4 * ARC_RADIUS;
+        let up = alternatives.first().map_or(0, |first| first.up);
+        let mut down = alternatives.first().map_or(0, |first| first.down);
+        let mut placed = Vec::with_capacity(alternatives.len());
+
+        for (index, alternative) in alternatives.into_iter().enumerate() {
+            let offset = if index == 0 {
+                0
+            } else {
+                (down + VERTICAL_SEPARATION + alternative.up).max(2 * ARC_RADIUS)
+            };
+
+            if index > 0 {
+                down = offset + alternative.down;
+            }
+
+            placed.push((alternative, offset));
+        }
+
+        Element {
+            kind: ElementKind::Choice(placed),
+            width,
+            up,
+            down,
+        }
+    }
+
+    fn optional(item: Element) -> Self {
+        Element::choice(vec![Element::skip(), item])
+    }
+
+    fn repeated(item: Element, separator: Element) -> Self {
+        let offset = (item.down + VERTICAL_SEPARATION + separator.up).max(2 * ARC_RADIUS);
+
+        Element {
+            width: item.width.max(separator.width) + 2 * ARC_RADIUS,
+            up: item.up,
+            down: offset + separator.down,
+            kind: ElementKind::Repeated(Box::new(item), Box::new(separator), offset),
+        }
+    }
+
+    fn draw(&self, x: i32, y: i32, svg: &mut String) {
+        match &self.kind {
+            ElementKind::Skip => {}
+            ElementKind::Box { text, class, link } => {
+                let radius = match *class {
+                    "terminal" => BOX_HEIGHT / 2,
+                    "nonterminal" => 0,
+                    _ => 4,
+                };
+
+                if let Some(link) = link {
+                    let link = escape(link);
+                    let _ = write!(svg, "<a href=\"{}\" xlink:href=\"{}\">", link, link);
+                }
+
+                let _ = writeln!(
+                    svg,
+                    "<rect class=\"{}\" x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" \
+                     rx=\"{}\" ry=\"{}\"/><text x=\"{}\" y=\"{}\">{}</text>",
+                    class,
+                    x,
+                    y - BOX_HEIGHT / 2,
+                    self.width,
+                    BOX_HEIGHT,
+                    radius,
+                    radius,
+                    x + self.width / 2,
+                    y + TEXT_BASELINE_OFFSET,
+                    escape(text)
+                );
+
+                if link.is_some() {
+                    svg.push_str("</a>\n");
+                }
+            }
+            ElementKind::Label(text) => {
+                let _ = writeln!(
+                    svg,
+                    "<path d=\"M {} {} h {}\"/><text class=\"label\" x=\"{}\" y=\"{}\">{}</text>",
+                    x,
+                    y,
+                    self.width,
+                    x + self.width / 2,
+                    y + BOX_HEIGHT / 2 + TEXT_BASELINE_OFFSET,
+                    escape(text)
+                );
+            }
+            ElementKind::Sequence(items) => {
+                let mut cursor = x;
+
+                for (index, item) in items.iter().enumerate() {
+                    if index > 0 {
+                        line(svg, cursor, y, HORIZONTAL_SEPARATION);
+                        cursor += HORIZONTAL_SEPARATION;
+                    }
+
+                    item.draw(cursor, y, svg);
+                    cursor += item.width;
+                }
+            }
+            ElementKind::Choice(alternatives) => {
+                let right = x + self.width;
+
+                for (index, (alternative, offset)) in alternatives.iter().enumerate() {
+                    let item_x = x + 2 * ARC_RADIUS;
+                    let item_y = y + offset;
+                    let item_end = item_x + alternative.width;
+
+                    if index == 0 {
+                        line(svg, x, y, 2 * ARC_RADIUS);
+                        line(svg, item_end, y, right - item_end);
+                    } else {
+                        let _ = writeln!(
+                            svg,
+                            "<path d=\"M {} {} a {r} {r} 0 0 1 {r} {r} V {} a {r} {r} 0 0 0 {r} {r}\"/>",
+                            x,
+                            y,
+                            item_y - ARC_RADIUS,
+                            r = ARC_RADIUS
+                        );
+                        let _ = writeln!(
+                            svg,
+                            "<path d=\"M {} {} H {} a {r} {r} 0 0 0 {r} -{r} V {} a {r} {r} 0 0 1 {r} -{r}\"/>",
+                            item_end,
+                            item_y,
+                            right - 2 * ARC_RADIUS,
+                            y + ARC_RADIUS,
+                            r = ARC_RADIUS
+                        );
+                    }
+
+                    alternative.draw(item_x, item_y, svg);
+                }
+            }
+            ElementKind::Repeated(item, separator, offset) => {
+                let right = x + self.width;
+                let item_end = x + ARC_RADIUS + item.width;
+                let loop_y = y + offset;
+
+                line(svg, x, y, ARC_RADIUS);
+                item.draw(x + ARC_RADIUS, y, svg);
+                line(svg, item_end, y, right - item_end);
+
+                let _ = writeln!(
+                    svg,
+                    "<path d=\"M {} {} a {r} {r} 0 0 1 {r} {r} V {} a {r} {r} 0 0 1 -{r} {r} \
+                     H {}\"/>",
+                    right - ARC_RADIUS,
+                    y,
+                    loop_y - ARC_RADIUS,
+                    x + ARC_RADIUS + separator.width,
+                    r = ARC_RADIUS
+                );
+                separator.draw(x + ARC_RADIUS, loop_y, svg);
+                let _ = writeln!(
+                    svg,
+                    "<path d=\"M {} {} a {r} {r} 0 0 1 -{r} -{r} V {} a {r} {r} 0 0 1 {r} -{r}\"/>",
+                    x + ARC_RADIUS,
+                    loop_y,
+                    y + ARC_RADIUS,
+                    r = ARC_RADIUS
+                );
+            }
+        }
+    }
+}
+
+fn line(svg: &mut String, x: i32, y: i32, length: i32) {
+    if length > 0 {
+        let _ = writeln!(svg, "<path d=\"M {} {} h {}\"/>", x, y, length);
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for character in text.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            character => escaped.push(character),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::Railroad,
+        crate::{
+            grammar::Grammar,
+            literals, ranges,
+            rules::{Literal, OneOrMore, Range},
+        },
+    };
+
+    literals!(Open: "<", Close: "/>", Ampersand: "&", Quote: "\"");
+    ranges!(Letter: ['a', 'z']);
+
+    #[derive(Clone, Debug, pegast_derive::PegAstNode)]
+    struct Tag {
+        open: Literal<Open>,
+        name: Name,
+        attributes: Vec<Attribute>,
+        close: Literal<Close>,
+    }
+
+    #[derive(Clone, Debug, pegast_derive::PegAstNode)]
+    struct Attribute {
+        ampersand: Literal<Ampersand>,
+        name: Name,
+        quote: Option<Literal<Quote>>,
+    }
+
+    #[derive(Clone, Debug, pegast_derive::PegAstNode)]
+    struct Name {
+        letters: OneOrMore<Range<Letter>>,
+    }
+
+    fn assert_well_formed(svg: &str) {
+        let mut open_elements = Vec::new();
+        let mut rest = svg;
+
+        while let Some(start) = rest.find(['<', '&']) {
+            let text = &rest[..start];
+
+            assert!(!text.contains('>'), "unescaped '>' in {:?}", text);
+            rest = &rest[start..];
+
+            if rest.starts_with('&') {
+                let end = rest.find(';').expect("unterminated entity");
+                let entity = &rest[1..end];
+
+                assert!(
+                    ["amp", "lt", "gt", "quot", "#39"].contains(&entity),
+                    "unknown entity {:?}",
+                    entity
+                );
+                rest = &rest[end + 1..];
+                continue;
+            }
+
+            let end = rest.find('>').expect("unterminated tag");
+            let tag = &rest[1..end];
+
+            assert!(!tag.contains('<'), "unescaped '<' in tag {:?}", tag);
+            assert_eq!(
+                tag.matches('"').count() % 2,
+                0,
+                "unbalanced quotes in {:?}",
+                tag
+            );
+
+            if let Some(name) = tag.strip_prefix('/') {
+                assert_eq!(open_elements.pop(), Some(name));
+            } else if !tag.ends_with('/') {
+                open_elements.push(tag.split_whitespace().next().unwrap());
+            }
+
+            rest = &rest[end + 1..];
+        }
+
+        assert!(open_elements.is_empty(), "unclosed {:?}", open_elements);
+    }
+
+    #[test]
+    fn renders_well_formed_linked_diagrams() {
+        let grammar = Grammar::of::<Tag>();
+        let railroad = Railroad::new(&grammar);
+
+        assert_eq!(railroad.rule_names(), ["Tag", "Name", "Attribute"]);
+
+        for (_, svg) in railroad.svgs() {
+            assert_well_formed(&svg);
+        }
+
+        let tag = railroad.rule_svg("Tag").unwrap();
+
+        assert!(tag.contains("<a href=\"#Name\" xlink:href=\"#Name\">"));
+        assert!(tag.contains("<a href=\"#Attribute\" xlink:href=\"#Attribute\">"));
+        assert!(tag.contains(">&quot;&lt;&quot;</text>"));
+        assert!(tag.contains(">&quot;/&gt;&quot;</text>"));
+
+        let attribute = railroad.rule_svg("Attribute").unwrap();
+
+        assert!(attribute.contains(">&quot;&amp;&quot;</text>"));
+        assert!(attribute.contains(">&quot;\\&quot;&quot;</text>"));
+    }
+
+    #[test]
+    fn escapes_links_and_looks_rules_up_by_their_exported_names() {
+        let grammar = Grammar::of::<Tag>();
+        let railroad = Railroad::new(&grammar).with_links("rules?name=", "&format=\"svg\"");
+        let tag = railroad.rule_svg("Tag").unwrap();
+
+        assert_well_formed(&tag);
+        assert!(tag.contains("href=\"rules?name=Name&amp;format=&quot;svg&quot;\""));
+        assert!(railroad.rule_svg(std::any::type_name::<Tag>()).is_none());
+        assert!(railroad.rule_svg("Missing").is_none());
+    }
+
+    #[test]
+    fn renders_a_page_with_every_diagram() {
+        let grammar = Grammar::of::<Tag>();
+        let railroad = Railroad::new(&grammar);
+        let html = railroad.html();
+        let body = &html[html.find("<body>").unwrap()..];
+
+        assert_well_formed(body.trim_end_matches("</html>\n"));
+
+        for (_, svg) in railroad.svgs() {
+            assert!(html.contains(&svg));
+        }
+
+        assert!(html.contains("<section id=\"Name\">\n<h2><a href=\"#Name\">Name</a></h2>"));
+    }
+}
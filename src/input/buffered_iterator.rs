@@ -30,15 +30,9 @@ where
     }
 
     pub fn advance(&mut self, count: usize) -> usize {
-        let initial_buffer_length = self.buffer.len();
+        let mut removed_count = count.min(self.buffer.len());
 
-        for _ in 0..count {
-            if self.buffer.pop_front().is_none() {
-                break;
-            }
-        }
-
-        let mut removed_count = initial_buffer_length;
+        self.buffer.drain(..removed_count);
 
         if count > removed_count {
             for _ in 0..(count - removed_count) {
@@ -60,12 +54,12 @@ where
 
 impl<T> Iterator for BufferedIterator<T>
 where
-    T: Iterator<Item = char>,
+    T: Iterator,
 {
-    type Item = char;
+    type Item = T::Item;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.buffer.len() > 0 {
+        if !self.buffer.is_empty() {
             self.buffer.pop_front()
         } else {
             self.source.next()
@@ -89,17 +83,13 @@ where
         }
     }
 
-    pub fn position(&self) -> usize {
-        self.position
-    }
-
     pub fn peek(&mut self, count: usize) -> impl Iterator<Item = &T::Item> {
         self.inner.peek(count + self.position).skip(self.position)
     }
 
     pub fn peek_into<'b>(&'b mut self) -> PeekingIntoBufferedIterator<'b, T> {
         PeekingIntoBufferedIterator {
-            inner: &mut self.inner,
+            inner: self.inner,
             position: self.position,
         }
     }
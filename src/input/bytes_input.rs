@@ -0,0 +1,212 @@
+use {
+    super::{element::decode_utf8, Input, MemoTable, ParseState, Position},
+    std::{borrow::BorrowMut, sync::Arc},
+};
+
+#[derive(Clone, Debug)]
+pub struct BytesInput<'s, S = ParseState> {
+    source: &'s [u8],
+    position: Position,
+    state: S,
+}
+
+impl<'s> BytesInput<'s> {
+    pub fn new(source: &'s [u8]) -> Self {
+        BytesInput {
+            source,
+            position: Position::new(),
+            state: ParseState::new(),
+        }
+    }
+}
+
+impl<'s, S> BytesInput<'s, S> {
+    pub fn source(&self) -> &'s [u8] {
+        self.source
+    }
+
+    pub fn remaining(&self) -> &'s [u8] {
+        &self.source[self.position.byte_offset..]
+    }
+
+    // Matched text is valid UTF-8, so it advances by characters like StrInput.
+    fn advance_over(&mut self, string: &str) {
+        for character in string.chars() {
+            self.position.advance(character);
+        }
+    }
+}
+
+impl<'s> From<&'s [u8]> for BytesInput<'s> {
+    fn from(source: &'s [u8]) -> Self {
+        BytesInput::new(source)
+    }
+}
+
+impl<'s, S> Input for BytesInput<'s, S>
+where
+    S: BorrowMut<ParseState>,
+{
+    fn position(&self) -> Position {
+        self.position
+    }
+
+    fn advance(&mut self, amount: usize) {
+        for _ in 0..amount {
+            if self.next_byte().is_none() {
+                break;
+            }
+        }
+    }
+
+    fn advance_to(&mut self, position: Position) {
+        assert!(
+            position >= self.position,
+            "Attempt to advance input backwards"
+        );
+
+        self.position = position;
+    }
+
+    fn check(&mut self, string: &str) -> bool {
+        self.check_bytes(string.as_bytes())
+    }
+
+    fn consume(&mut self, string: &str) -> bool {
+        if self.check(string) {
+            self.advance_over(string);
+
+            true
+        } else {
+            false
+        }
+    }
+
+    fn check_ignore_case(&mut self, string: &str) -> bool {
+        let remaining = self.remaining();
+
+        remaining.len() >= string.len()
+            && remaining[..string.len()].eq_ignore_ascii_case(string.as_bytes())
+    }
+
+    fn consume_ignore_case(&mut self, string: &str) -> bool {
+        if self.check_ignore_case(string) {
+            self.advance_over(string);
+
+            true
+        } else {
+            false
+        }
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let (character, length) = decode_utf8(self.remaining())?;
+
+        if character.len_utf8() == length {
+            self.position.advance(character);
+        } else {
+            self.next_byte();
+        }
+
+        Some(character)
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        decode_utf8(self.remaining()).map(|(character, _)| character)
+    }
+
+    fn check_bytes(&mut self, bytes: &[u8]) -> bool {
+        self.remaining().starts_with(bytes)
+    }
+
+    fn consume_bytes(&mut self, bytes: &[u8]) -> bool {
+        if self.check_bytes(bytes) {
+            for &byte in bytes {
+                self.position.advance_byte(byte);
+            }
+
+            true
+        } else {
+            false
+        }
+    }
+
+    fn next_byte(&mut self) -> Option<u8> {
+        let result = self.peek_byte();
+
+        if let Some(byte) = result {
+            self.position.advance_byte(byte);
+        }
+
+        result
+    }
+
+    fn peek_byte(&mut self) -> Option<u8> {
+        self.remaining().first().cloned()
+    }
+
+    fn shared_source(&self) -> Option<&Arc<str>> {
+        None
+    }
+
+    fn memo_table(&mut self) -> Option<&mut MemoTable> {
+        None
+    }
+
+    fn state(&mut self) -> &mut ParseState {
+        self.state.borrow_mut()
+    }
+
+    fn peek_only<'a>(&'a mut self) -> Box<dyn Input + 'a> {
+        Box::new(BytesInput {
+            source: self.source,
+            position: self.position,
+            state: self.state.borrow_mut(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::BytesInput,
+        crate::{
+            byte_literals, parse_complete,
+            rules::{Any, AnyByte, ByteLiteral},
+            Input, PegAstNode,
+        },
+    };
+
+    byte_literals!(Accent: b"\xc3\xa9");
+
+    #[test]
+    fn decodes_characters_as_utf8() {
+        let mut input = BytesInput::new("é!".as_bytes());
+        let (accent, bang) = parse_complete::<(Any, Any)>(&mut input).unwrap();
+
+        assert_eq!(accent.parsed_string(), "é");
+        assert_eq!(bang.parsed_string(), "!");
+        assert_eq!(input.position().byte_offset, 3);
+    }
+
+    #[test]
+    fn replaces_invalid_utf8_with_replacement_characters() {
+        let mut input = BytesInput::new(b"\xffa");
+        let (invalid, letter) = parse_complete::<(Any, Any)>(&mut input).unwrap();
+
+        assert_eq!(invalid.parsed_string(), "\u{fffd}");
+        assert_eq!(letter.parsed_string(), "a");
+    }
+
+    #[test]
+    fn byte_nodes_decode_their_bytes_lossily() {
+        let literal = parse_complete::<ByteLiteral<Accent>>(&mut BytesInput::new(b"\xc3\xa9"));
+        let (first, second) =
+            parse_complete::<(AnyByte, AnyByte)>(&mut BytesInput::new(b"\xc3\xa9")).unwrap();
+
+        assert_eq!(literal.unwrap().parsed_string(), "é");
+        assert_eq!((first.byte(), second.byte()), (0xc3, 0xa9));
+        assert_eq!(first.parsed_string(), "\u{fffd}");
+        assert_eq!(first.parsed_string_length(), 1);
+    }
+}
@@ -0,0 +1,147 @@
+use {super::Position, std::str};
+
+pub trait InputElement: Copy + Eq {
+    fn to_char(self) -> char;
+    fn to_byte(self) -> Option<u8>;
+    fn offset(position: &Position) -> usize;
+    fn advance_position(self, position: &mut Position);
+    fn str_length(string: &str) -> usize;
+    fn matches_str<'a>(elements: impl Iterator<Item = &'a Self>, string: &str) -> bool
+    where
+        Self: 'a;
+    fn matches_str_ignore_case<'a>(elements: impl Iterator<Item = &'a Self>, string: &str) -> bool
+    where
+        Self: 'a;
+
+    fn matches_bytes<'a>(elements: impl Iterator<Item = &'a Self>, bytes: &[u8]) -> bool
+    where
+        Self: 'a,
+    {
+        let mut count = 0;
+
+        for (element, byte) in elements.zip(bytes) {
+            if element.to_byte() != Some(*byte) {
+                return false;
+            }
+
+            count += 1;
+        }
+
+        count == bytes.len()
+    }
+}
+
+impl InputElement for char {
+    fn to_char(self) -> char {
+        self
+    }
+
+    fn to_byte(self) -> Option<u8> {
+        if self.is_ascii() {
+            Some(self as u8)
+        } else {
+            None
+        }
+    }
+
+    fn offset(position: &Position) -> usize {
+        position.char_offset
+    }
+
+    fn advance_position(self, position: &mut Position) {
+        position.advance(self);
+    }
+
+    fn str_length(string: &str) -> usize {
+        string.chars().count()
+    }
+
+    fn matches_str<'a>(elements: impl Iterator<Item = &'a Self>, string: &str) -> bool {
+        let mut count = 0;
+
+        for (a, b) in string.chars().zip(elements) {
+            if a != *b {
+                return false;
+            }
+
+            count += 1;
+        }
+
+        count == Self::str_length(string)
+    }
+
+    fn matches_str_ignore_case<'a>(elements: impl Iterator<Item = &'a Self>, string: &str) -> bool {
+        let mut count = 0;
+
+        for (a, b) in string.chars().zip(elements) {
+            if !chars_eq_ignore_case(a, *b) {
+                return false;
+            }
+
+            count += 1;
+        }
+
+        count == Self::str_length(string)
+    }
+}
+
+impl InputElement for u8 {
+    fn to_char(self) -> char {
+        if self.is_ascii() {
+            char::from(self)
+        } else {
+            char::REPLACEMENT_CHARACTER
+        }
+    }
+
+    fn to_byte(self) -> Option<u8> {
+        Some(self)
+    }
+
+    fn offset(position: &Position) -> usize {
+        position.byte_offset
+    }
+
+    fn advance_position(self, position: &mut Position) {
+        position.advance_byte(self);
+    }
+
+    fn str_length(string: &str) -> usize {
+        string.len()
+    }
+
+    fn matches_str<'a>(elements: impl Iterator<Item = &'a Self>, string: &str) -> bool {
+        Self::matches_bytes(elements, string.as_bytes())
+    }
+
+    fn matches_str_ignore_case<'a>(elements: impl Iterator<Item = &'a Self>, string: &str) -> bool {
+        let mut count = 0;
+
+        for (element, byte) in elements.zip(string.as_bytes()) {
+            if !element.eq_ignore_ascii_case(byte) {
+                return false;
+            }
+
+            count += 1;
+        }
+
+        count == string.len()
+    }
+}
+
+pub fn chars_eq_ignore_case(a: char, b: char) -> bool {
+    a == b || a.to_lowercase().eq(b.to_lowercase())
+}
+
+pub fn decode_utf8(bytes: &[u8]) -> Option<(char, usize)> {
+    let prefix = &bytes[..bytes.len().min(4)];
+    let valid = match str::from_utf8(prefix) {
+        Ok(valid) => valid,
+        Err(error) => str::from_utf8(&prefix[..error.valid_up_to()]).expect("Valid UTF-8 prefix"),
+    };
+
+    match valid.chars().next() {
+        Some(character) => Some((character, character.len_utf8())),
+        None => bytes.first().map(|_| (char::REPLACEMENT_CHARACTER, 1)),
+    }
+}
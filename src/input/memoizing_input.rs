@@ -0,0 +1,246 @@
+use {
+    super::{Input, ParseState, Position},
+    crate::ParseError,
+    std::{
+        any::{Any, TypeId},
+        borrow::BorrowMut,
+        collections::{HashMap, HashSet},
+        sync::Arc,
+    },
+};
+
+type MemoKey = (TypeId, usize);
+
+enum MemoEntry {
+    InProgress { left_recursive: bool },
+    Growing(Box<dyn Any>),
+    Done(Box<dyn Any>),
+}
+
+pub(crate) enum MemoLookup<T> {
+    Missing,
+    LeftRecursion,
+    Found(Result<(T, Position), ParseError>),
+}
+
+#[derive(Default)]
+pub struct MemoTable {
+    entries: HashMap<MemoKey, MemoEntry>,
+    in_progress: Vec<MemoKey>,
+    involved: HashSet<MemoKey>,
+}
+
+impl MemoTable {
+    pub fn new() -> Self {
+        MemoTable::default()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.in_progress.clear();
+        self.involved.clear();
+    }
+
+    pub(crate) fn lookup<T>(&mut self, position: Position) -> MemoLookup<T>
+    where
+        T: Clone + 'static,
+    {
+        let key = Self::key::<T>(position);
+
+        match self.entries.get_mut(&key) {
+            None => MemoLookup::Missing,
+            Some(MemoEntry::Done(result)) => MemoLookup::Found(
+                Self::downcast(result.as_ref()).expect("Memoized result has wrong type"),
+            ),
+            Some(MemoEntry::Growing(result)) => {
+                let result =
+                    Self::downcast(result.as_ref()).expect("Memoized result has wrong type");
+
+                self.mark_involved_in(key);
+
+                MemoLookup::Found(result)
+            }
+            Some(MemoEntry::InProgress { left_recursive }) => {
+                *left_recursive = true;
+
+                self.mark_involved_in(key);
+
+                MemoLookup::LeftRecursion
+            }
+        }
+    }
+
+    pub(crate) fn start<T>(&mut self, position: Position)
+    where
+        T: 'static,
+    {
+        let key = Self::key::<T>(position);
+
+        self.entries.insert(
+            key,
+            MemoEntry::InProgress {
+                left_recursive: false,
+            },
+        );
+        self.in_progress.push(key);
+    }
+
+    pub(crate) fn is_left_recursive<T>(&self, position: Position) -> bool
+    where
+        T: 'static,
+    {
+        match self.entries.get(&Self::key::<T>(position)) {
+            Some(MemoEntry::InProgress { left_recursive }) => *left_recursive,
+            Some(MemoEntry::Growing(_)) => true,
+            _ => false,
+        }
+    }
+
+    pub(crate) fn grow<T>(&mut self, position: Position, seed: Result<(T, Position), ParseError>)
+    where
+        T: 'static,
+    {
+        self.entries
+            .insert(Self::key::<T>(position), MemoEntry::Growing(Box::new(seed)));
+    }
+
+    pub(crate) fn finish<T>(
+        &mut self,
+        position: Position,
+        result: Result<(T, Position), ParseError>,
+    ) where
+        T: 'static,
+    {
+        let key = Self::key::<T>(position);
+        let finished = self.in_progress.pop();
+
+        assert_eq!(finished, Some(key), "Memoized rules finished out of order");
+
+        if self.involved.remove(&key) {
+            self.entries.remove(&key);
+        } else {
+            self.entries.insert(key, MemoEntry::Done(Box::new(result)));
+        }
+    }
+
+    fn mark_involved_in(&mut self, head: MemoKey) {
+        if let Some(index) = self.in_progress.iter().rposition(|&key| key == head) {
+            self.involved
+                .extend(self.in_progress[index + 1..].iter().cloned());
+        }
+    }
+
+    fn key<T>(position: Position) -> MemoKey
+    where
+        T: 'static,
+    {
+        (TypeId::of::<T>(), position.byte_offset)
+    }
+
+    fn downcast<T>(result: &dyn Any) -> Option<Result<(T, Position), ParseError>>
+    where
+        T: Clone + 'static,
+    {
+        result
+            .downcast_ref::<Result<(T, Position), ParseError>>()
+            .cloned()
+    }
+}
+
+pub struct MemoizingInput<I, M = MemoTable> {
+    inner: I,
+    table: M,
+}
+
+impl<I> MemoizingInput<I>
+where
+    I: Input,
+{
+    pub fn new(inner: I) -> Self {
+        MemoizingInput {
+            inner,
+            table: MemoTable::new(),
+        }
+    }
+
+    pub fn into_inner(self) -> I {
+        self.inner
+    }
+}
+
+impl<I, M> Input for MemoizingInput<I, M>
+where
+    I: Input,
+    M: BorrowMut<MemoTable>,
+{
+    fn position(&self) -> Position {
+        self.inner.position()
+    }
+
+    fn advance(&mut self, amount: usize) {
+        self.inner.advance(amount)
+    }
+
+    fn advance_to(&mut self, position: Position) {
+        self.inner.advance_to(position)
+    }
+
+    fn check(&mut self, string: &str) -> bool {
+        self.inner.check(string)
+    }
+
+    fn consume(&mut self, string: &str) -> bool {
+        self.inner.consume(string)
+    }
+
+    fn check_ignore_case(&mut self, string: &str) -> bool {
+        self.inner.check_ignore_case(string)
+    }
+
+    fn consume_ignore_case(&mut self, string: &str) -> bool {
+        self.inner.consume_ignore_case(string)
+    }
+
+    fn next(&mut self) -> Option<char> {
+        self.inner.next()
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.inner.peek()
+    }
+
+    fn check_bytes(&mut self, bytes: &[u8]) -> bool {
+        self.inner.check_bytes(bytes)
+    }
+
+    fn consume_bytes(&mut self, bytes: &[u8]) -> bool {
+        self.inner.consume_bytes(bytes)
+    }
+
+    fn next_byte(&mut self) -> Option<u8> {
+        self.inner.next_byte()
+    }
+
+    fn peek_byte(&mut self) -> Option<u8> {
+        self.inner.peek_byte()
+    }
+
+    fn shared_source(&self) -> Option<&Arc<str>> {
+        self.inner.shared_source()
+    }
+
+    fn memo_table(&mut self) -> Option<&mut MemoTable> {
+        Some(self.table.borrow_mut())
+    }
+
+    fn state(&mut self) -> &mut ParseState {
+        self.inner.state()
+    }
+
+    fn peek_only<'a>(&'a mut self) -> Box<dyn Input + 'a> {
+        Box::new(MemoizingInput {
+            inner: self.inner.peek_only(),
+            table: self.table.borrow_mut(),
+        })
+    }
+}
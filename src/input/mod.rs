@@ -1,78 +1,129 @@
 mod buffered_iterator;
-
-use self::buffered_iterator::{BufferedIterator, PeekingIntoBufferedIterator};
+mod bytes_input;
+mod element;
+mod memoizing_input;
+mod parse_state;
+mod position;
+mod source_slice;
+mod span;
+mod str_input;
+
+pub use self::{
+    bytes_input::BytesInput,
+    element::InputElement,
+    memoizing_input::{MemoTable, MemoizingInput},
+    parse_state::{Checkpoint, ParseState},
+    position::Position,
+    source_slice::SourceSlice,
+    span::Span,
+    str_input::StrInput,
+};
+
+pub(crate) use self::{memoizing_input::MemoLookup, parse_state::SideEffects};
+
+use {
+    self::buffered_iterator::{BufferedIterator, PeekingIntoBufferedIterator},
+    std::sync::Arc,
+};
 
 pub trait Input {
-    fn position(&self) -> usize;
+    fn position(&self) -> Position;
     fn advance(&mut self, amount: usize);
-    fn advance_to(&mut self, position: usize);
+    fn advance_to(&mut self, position: Position);
     fn check(&mut self, string: &str) -> bool;
     fn consume(&mut self, string: &str) -> bool;
+    fn check_ignore_case(&mut self, string: &str) -> bool;
+    fn consume_ignore_case(&mut self, string: &str) -> bool;
     fn next(&mut self) -> Option<char>;
     fn peek(&mut self) -> Option<char>;
+    fn check_bytes(&mut self, bytes: &[u8]) -> bool;
+    fn consume_bytes(&mut self, bytes: &[u8]) -> bool;
+    fn next_byte(&mut self) -> Option<u8>;
+    fn peek_byte(&mut self) -> Option<u8>;
+    fn shared_source(&self) -> Option<&Arc<str>>;
+    fn memo_table(&mut self) -> Option<&mut MemoTable>;
+    fn state(&mut self) -> &mut ParseState;
     // TODO: Use generic associated types when available
     fn peek_only<'a>(&'a mut self) -> Box<dyn Input + 'a>;
 }
 
-pub struct ConsumingInput<T: Iterator<Item = char>> {
-    position: usize,
+pub struct ConsumingInput<T: Iterator>
+where
+    T::Item: InputElement,
+{
+    position: Position,
     iterator: BufferedIterator<T>,
+    state: ParseState,
 }
 
 impl<T> ConsumingInput<T>
 where
-    T: Iterator<Item = char>,
+    T: Iterator,
+    T::Item: InputElement,
 {
-    pub fn new(source: impl IntoIterator<Item = char, IntoIter = T>) -> Self {
+    pub fn new(source: impl IntoIterator<Item = T::Item, IntoIter = T>) -> Self {
         ConsumingInput {
-            position: 0,
+            position: Position::new(),
             iterator: BufferedIterator::new(source),
+            state: ParseState::new(),
         }
     }
 }
 
 impl<T> Input for ConsumingInput<T>
 where
-    T: Iterator<Item = char>,
+    T: Iterator,
+    T::Item: InputElement,
 {
-    fn position(&self) -> usize {
+    fn position(&self) -> Position {
         self.position
     }
 
     fn advance(&mut self, amount: usize) {
+        for element in self.iterator.peek(amount) {
+            element.advance_position(&mut self.position);
+        }
+
         self.iterator.advance(amount);
     }
 
-    fn advance_to(&mut self, position: usize) {
+    fn advance_to(&mut self, position: Position) {
         assert!(
             position >= self.position,
             "Attempt to advance input backwards"
         );
 
-        self.advance(position - self.position);
+        let offset = T::Item::offset(&self.position);
+
+        self.iterator.advance(T::Item::offset(&position) - offset);
+        self.position = position;
     }
 
     fn check(&mut self, string: &str) -> bool {
-        let mut count = 0;
+        let length = T::Item::str_length(string);
 
-        for (a, b) in string.chars().zip(self.iterator.peek(string.len())) {
-            if a != *b {
-                return false;
-            }
+        T::Item::matches_str(self.iterator.peek(length), string)
+    }
 
-            count += 1;
-        }
+    fn consume(&mut self, string: &str) -> bool {
+        if self.check(string) {
+            self.advance(T::Item::str_length(string));
 
-        if count == string.len() {
             true
         } else {
             false
         }
     }
 
-    fn consume(&mut self, string: &str) -> bool {
-        if self.check(string) {
-            self.advance(string.len());
+    fn check_ignore_case(&mut self, string: &str) -> bool {
+        let length = T::Item::str_length(string);
+
+        T::Item::matches_str_ignore_case(self.iterator.peek(length), string)
+    }
+
+    fn consume_ignore_case(&mut self, string: &str) -> bool {
+        if self.check_ignore_case(string) {
+            self.advance(T::Item::str_length(string));
 
             true
         } else {
@@ -83,6 +134,37 @@ where
     fn next(&mut self) -> Option<char> {
         let result = self.iterator.next();
 
+        if let Some(element) = result {
+            element.advance_position(&mut self.position);
+        }
+
+        result.map(InputElement::to_char)
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.iterator
+            .peek(1)
+            .next()
+            .map(|element| element.to_char())
+    }
+
+    fn check_bytes(&mut self, bytes: &[u8]) -> bool {
+        T::Item::matches_bytes(self.iterator.peek(bytes.len()), bytes)
+    }
+
+    fn consume_bytes(&mut self, bytes: &[u8]) -> bool {
+        if self.check_bytes(bytes) {
+            self.advance(bytes.len());
+
+            true
+        } else {
+            false
+        }
+    }
+
+    fn next_byte(&mut self) -> Option<u8> {
+        let result = self.peek_byte();
+
         if result.is_some() {
             self.advance(1);
         }
@@ -90,74 +172,115 @@ where
         result
     }
 
-    fn peek(&mut self) -> Option<char> {
-        self.iterator.peek(1).next().cloned()
+    fn peek_byte(&mut self) -> Option<u8> {
+        self.iterator
+            .peek(1)
+            .next()
+            .and_then(|element| element.to_byte())
+    }
+
+    fn shared_source(&self) -> Option<&Arc<str>> {
+        None
+    }
+
+    fn memo_table(&mut self) -> Option<&mut MemoTable> {
+        None
+    }
+
+    fn state(&mut self) -> &mut ParseState {
+        &mut self.state
     }
 
     fn peek_only<'a>(&'a mut self) -> Box<dyn Input + 'a> {
-        Box::new(PeekingInput::new(self.position, &mut self.iterator))
+        Box::new(PeekingInput::new(
+            self.position,
+            &mut self.iterator,
+            &mut self.state,
+        ))
     }
 }
 
 pub struct PeekingInput<'a, T: Iterator> {
-    start_position: usize,
+    position: Position,
     iterator: PeekingIntoBufferedIterator<'a, T>,
+    state: &'a mut ParseState,
 }
 
 impl<'a, T> PeekingInput<'a, T>
 where
-    T: Iterator<Item = char>,
+    T: Iterator,
+    T::Item: InputElement,
 {
-    pub fn new(start_position: usize, target: &'a mut BufferedIterator<T>) -> Self {
+    pub fn new(
+        position: Position,
+        target: &'a mut BufferedIterator<T>,
+        state: &'a mut ParseState,
+    ) -> Self {
         PeekingInput {
-            start_position,
+            position,
             iterator: PeekingIntoBufferedIterator::new(target),
+            state,
         }
     }
 }
 
 impl<'i, T> Input for PeekingInput<'i, T>
 where
-    T: Iterator<Item = char>,
+    T: Iterator,
+    T::Item: InputElement,
 {
-    fn position(&self) -> usize {
-        self.start_position + self.iterator.position()
+    fn position(&self) -> Position {
+        self.position
     }
 
     fn advance(&mut self, amount: usize) {
-        self.iterator.advance(amount);
+        let mut count = 0;
+
+        for element in self.iterator.peek(amount) {
+            element.advance_position(&mut self.position);
+            count += 1;
+        }
+
+        self.iterator.advance(count);
     }
 
-    fn advance_to(&mut self, position: usize) {
+    fn advance_to(&mut self, position: Position) {
         assert!(
-            position >= self.position(),
+            position >= self.position,
             "Attempt to advance input backwards"
         );
 
-        self.iterator.advance(position - self.position());
+        let offset = T::Item::offset(&self.position);
+
+        self.iterator.advance(T::Item::offset(&position) - offset);
+        self.position = position;
     }
 
     fn check(&mut self, string: &str) -> bool {
-        let mut count = 0;
+        let length = T::Item::str_length(string);
 
-        for (a, b) in string.chars().zip(self.iterator.peek(string.len())) {
-            if a != *b {
-                return false;
-            }
+        T::Item::matches_str(self.iterator.peek(length), string)
+    }
 
-            count += 1;
-        }
+    fn consume(&mut self, string: &str) -> bool {
+        if self.check(string) {
+            self.advance(T::Item::str_length(string));
 
-        if count == string.len() {
             true
         } else {
             false
         }
     }
 
-    fn consume(&mut self, string: &str) -> bool {
-        if self.check(string) {
-            self.advance(string.len());
+    fn check_ignore_case(&mut self, string: &str) -> bool {
+        let length = T::Item::str_length(string);
+
+        T::Item::matches_str_ignore_case(self.iterator.peek(length), string)
+    }
+
+    fn consume_ignore_case(&mut self, string: &str) -> bool {
+        if self.check_ignore_case(string) {
+            self.advance(T::Item::str_length(string));
 
             true
         } else {
@@ -166,17 +289,70 @@ where
     }
 
     fn next(&mut self) -> Option<char> {
-        self.iterator.next()
+        let result = self.iterator.next();
+
+        if let Some(element) = result {
+            element.advance_position(&mut self.position);
+        }
+
+        result.map(InputElement::to_char)
     }
 
     fn peek(&mut self) -> Option<char> {
-        self.iterator.peek(1).next().cloned()
+        self.iterator
+            .peek(1)
+            .next()
+            .map(|element| element.to_char())
+    }
+
+    fn check_bytes(&mut self, bytes: &[u8]) -> bool {
+        T::Item::matches_bytes(self.iterator.peek(bytes.len()), bytes)
+    }
+
+    fn consume_bytes(&mut self, bytes: &[u8]) -> bool {
+        if self.check_bytes(bytes) {
+            self.advance(bytes.len());
+
+            true
+        } else {
+            false
+        }
+    }
+
+    fn next_byte(&mut self) -> Option<u8> {
+        let result = self.peek_byte();
+
+        if result.is_some() {
+            self.advance(1);
+        }
+
+        result
+    }
+
+    fn peek_byte(&mut self) -> Option<u8> {
+        self.iterator
+            .peek(1)
+            .next()
+            .and_then(|element| element.to_byte())
+    }
+
+    fn shared_source(&self) -> Option<&Arc<str>> {
+        None
+    }
+
+    fn memo_table(&mut self) -> Option<&mut MemoTable> {
+        None
+    }
+
+    fn state(&mut self) -> &mut ParseState {
+        self.state
     }
 
     fn peek_only<'a>(&'a mut self) -> Box<dyn Input + 'a> {
         Box::new(PeekingInput {
             iterator: self.iterator.peek_into(),
-            start_position: self.start_position,
+            position: self.position,
+            state: self.state,
         })
     }
 }
@@ -185,7 +361,7 @@ impl<T> Input for Box<T>
 where
     T: Input + ?Sized,
 {
-    fn position(&self) -> usize {
+    fn position(&self) -> Position {
         self.as_ref().position()
     }
 
@@ -193,7 +369,7 @@ where
         self.as_mut().advance(amount)
     }
 
-    fn advance_to(&mut self, position: usize) {
+    fn advance_to(&mut self, position: Position) {
         self.as_mut().advance_to(position)
     }
 
@@ -205,6 +381,14 @@ where
         self.as_mut().consume(string)
     }
 
+    fn check_ignore_case(&mut self, string: &str) -> bool {
+        self.as_mut().check_ignore_case(string)
+    }
+
+    fn consume_ignore_case(&mut self, string: &str) -> bool {
+        self.as_mut().consume_ignore_case(string)
+    }
+
     fn next(&mut self) -> Option<char> {
         self.as_mut().next()
     }
@@ -213,6 +397,34 @@ where
         self.as_mut().peek()
     }
 
+    fn check_bytes(&mut self, bytes: &[u8]) -> bool {
+        self.as_mut().check_bytes(bytes)
+    }
+
+    fn consume_bytes(&mut self, bytes: &[u8]) -> bool {
+        self.as_mut().consume_bytes(bytes)
+    }
+
+    fn next_byte(&mut self) -> Option<u8> {
+        self.as_mut().next_byte()
+    }
+
+    fn peek_byte(&mut self) -> Option<u8> {
+        self.as_mut().peek_byte()
+    }
+
+    fn shared_source(&self) -> Option<&Arc<str>> {
+        self.as_ref().shared_source()
+    }
+
+    fn memo_table(&mut self) -> Option<&mut MemoTable> {
+        self.as_mut().memo_table()
+    }
+
+    fn state(&mut self) -> &mut ParseState {
+        self.as_mut().state()
+    }
+
     fn peek_only<'a>(&'a mut self) -> Box<dyn Input + 'a> {
         self.as_mut().peek_only()
     }
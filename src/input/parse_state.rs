@@ -0,0 +1,163 @@
+use {
+    super::Position,
+    crate::{cst::CstEvent, ParseError},
+};
+
+#[derive(Clone, Debug, Default)]
+pub struct ParseState {
+    furthest_failure: Option<ParseError>,
+    recovering: bool,
+    errors: Vec<ParseError>,
+    cst_events: Option<Vec<CstEvent>>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Checkpoint {
+    errors: usize,
+    events: usize,
+}
+
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SideEffects {
+    furthest_failure: Option<ParseError>,
+    errors: Vec<ParseError>,
+    events: Vec<CstEvent>,
+}
+
+impl ParseState {
+    pub fn new() -> Self {
+        ParseState::default()
+    }
+
+    pub fn enable_recovery(&mut self) {
+        self.recovering = true;
+    }
+
+    pub fn is_recovering(&self) -> bool {
+        self.recovering
+    }
+
+    pub fn errors(&self) -> &[ParseError] {
+        &self.errors
+    }
+
+    pub fn record_error(&mut self, error: ParseError) {
+        self.errors.push(error);
+    }
+
+    pub fn take_errors(&mut self) -> Vec<ParseError> {
+        std::mem::take(&mut self.errors)
+    }
+
+    pub fn enable_cst(&mut self) {
+        self.cst_events.get_or_insert_with(Vec::new);
+    }
+
+    pub fn start_node(&mut self, kind: &'static str, position: Position) {
+        self.push_event(CstEvent::Start { kind, position });
+    }
+
+    pub fn start_trivia(&mut self, position: Position) {
+        self.push_event(CstEvent::StartTrivia(position));
+    }
+
+    pub fn mark_boundary(&mut self, position: Position) {
+        self.push_event(CstEvent::Boundary(position));
+    }
+
+    // Leaf rules mark the range they consumed, so the CST gets one token per
+    // leaf instead of one per field of the enclosing node.
+    pub fn mark_token(&mut self, start: Position, end: Position) {
+        self.mark_boundary(start);
+        self.mark_boundary(end);
+    }
+
+    pub fn finish_node(&mut self, position: Position) {
+        self.push_event(CstEvent::Finish(position));
+    }
+
+    pub fn take_cst_events(&mut self) -> Vec<CstEvent> {
+        self.cst_events.take().unwrap_or_default()
+    }
+
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            errors: self.errors.len(),
+            events: self.cst_events.as_ref().map(Vec::len).unwrap_or(0),
+        }
+    }
+
+    pub fn rollback(&mut self, checkpoint: Checkpoint) {
+        self.errors.truncate(checkpoint.errors);
+
+        if let Some(events) = &mut self.cst_events {
+            events.truncate(checkpoint.events);
+        }
+    }
+
+    pub(crate) fn side_effects_since(
+        &self,
+        checkpoint: Checkpoint,
+        furthest_failure: Option<ParseError>,
+    ) -> SideEffects {
+        SideEffects {
+            furthest_failure,
+            errors: self.errors[checkpoint.errors..].to_vec(),
+            events: self
+                .cst_events
+                .as_ref()
+                .map(|events| events[checkpoint.events..].to_vec())
+                .unwrap_or_default(),
+        }
+    }
+
+    pub(crate) fn replay(&mut self, side_effects: &SideEffects) {
+        if let Some(failure) = &side_effects.furthest_failure {
+            self.record_failure(failure.clone());
+        }
+
+        self.errors.extend(side_effects.errors.iter().cloned());
+
+        if let Some(events) = &mut self.cst_events {
+            events.extend(side_effects.events.iter().cloned());
+        }
+    }
+
+    fn push_event(&mut self, event: CstEvent) {
+        if let Some(events) = &mut self.cst_events {
+            events.push(event);
+        }
+    }
+
+    pub fn furthest_failure(&self) -> Option<&ParseError> {
+        self.furthest_failure.as_ref()
+    }
+
+    pub fn take_furthest_failure(&mut self) -> Option<ParseError> {
+        self.furthest_failure.take()
+    }
+
+    pub fn set_furthest_failure(&mut self, failure: Option<ParseError>) {
+        self.furthest_failure = failure;
+    }
+
+    pub fn record_failure(&mut self, mut error: ParseError) {
+        error.cut = false;
+
+        self.furthest_failure = Some(match self.furthest_failure.take() {
+            Some(furthest_failure) => furthest_failure.merge(error),
+            None => error,
+        });
+    }
+
+    pub fn fail(&mut self, error: ParseError) -> ParseError {
+        let cut = error.cut;
+
+        self.record_failure(error);
+
+        self.furthest_failure
+            .clone()
+            .expect("Missing recorded failure")
+            .cut_if(cut)
+    }
+}
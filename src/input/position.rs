@@ -0,0 +1,129 @@
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Position {
+    pub byte_offset: usize,
+    pub char_offset: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Position {
+    pub fn new() -> Self {
+        Position {
+            byte_offset: 0,
+            char_offset: 0,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    pub fn advance(&mut self, character: char) {
+        self.byte_offset += character.len_utf8();
+        self.char_offset += 1;
+
+        if character == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+    }
+
+    pub fn advance_byte(&mut self, byte: u8) {
+        self.byte_offset += 1;
+        self.char_offset += 1;
+
+        if byte == b'\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+    }
+}
+
+impl Default for Position {
+    fn default() -> Self {
+        Position::new()
+    }
+}
+
+impl Display for Position {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "{}:{}", self.line, self.column)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::Position,
+        crate::{
+            input::{BytesInput, StrInput},
+            literals, parse_complete,
+            rules::{Literal, Spanned},
+        },
+    };
+
+    literals!(Header: "é\r\n", Word: "naïve", Newline: "\r\n");
+
+    fn position(byte_offset: usize, char_offset: usize, line: usize, column: usize) -> Position {
+        Position {
+            byte_offset,
+            char_offset,
+            line,
+            column,
+        }
+    }
+
+    #[test]
+    fn counts_lines_and_columns_in_characters() {
+        let mut position = Position::new();
+
+        for character in "é\r\nab".chars() {
+            position.advance(character);
+        }
+
+        assert_eq!(position, self::position(6, 5, 2, 3));
+        assert_eq!(position.to_string(), "2:3");
+    }
+
+    #[test]
+    fn starts_a_line_after_carriage_return_line_feed() {
+        let mut position = Position::new();
+
+        position.advance('\r');
+        assert_eq!(position, self::position(1, 1, 1, 2));
+
+        position.advance('\n');
+        assert_eq!(position, self::position(2, 2, 2, 1));
+    }
+
+    #[test]
+    fn spans_cover_multibyte_text_after_line_breaks() {
+        let source = "é\r\nnaïve\r\n";
+        let (_, word, _) =
+            parse_complete::<(Literal<Header>, Spanned<Literal<Word>>, Literal<Newline>)>(
+                &mut StrInput::new(source),
+            )
+            .unwrap();
+
+        assert_eq!(word.span().start, position(4, 3, 2, 1));
+        assert_eq!(word.span().end, position(10, 8, 2, 6));
+        assert_eq!(&source[word.span().byte_range()], "naïve");
+    }
+
+    #[test]
+    fn byte_inputs_track_the_same_positions() {
+        let source = "é\r\nnaïve\r\n";
+        let (_, word, _) =
+            parse_complete::<(Literal<Header>, Spanned<Literal<Word>>, Literal<Newline>)>(
+                &mut BytesInput::new(source.as_bytes()),
+            )
+            .unwrap();
+
+        assert_eq!(word.span().start, position(4, 3, 2, 1));
+        assert_eq!(word.span().end, position(10, 8, 2, 6));
+    }
+}
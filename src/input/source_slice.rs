@@ -0,0 +1,89 @@
+use std::{borrow::Cow, sync::Arc};
+
+#[derive(Clone, Copy, Debug)]
+pub enum SourceSlice<'a> {
+    Empty,
+    Unanchored(usize),
+    Anchored {
+        source: &'a Arc<str>,
+        start: usize,
+        end: usize,
+    },
+    Detached,
+}
+
+impl<'a> SourceSlice<'a> {
+    pub fn anchored(source: &'a Arc<str>, start: usize, end: usize) -> Self {
+        SourceSlice::Anchored { source, start, end }
+    }
+
+    pub fn of_text(text: &str) -> Self {
+        if text.is_empty() {
+            SourceSlice::Empty
+        } else {
+            SourceSlice::Unanchored(text.len())
+        }
+    }
+
+    pub fn join(slices: impl IntoIterator<Item = SourceSlice<'a>>) -> Self {
+        let mut joined = SourceSlice::Empty;
+
+        for slice in slices {
+            joined = joined.then(slice);
+
+            if let SourceSlice::Detached = joined {
+                break;
+            }
+        }
+
+        joined
+    }
+
+    pub fn then(self, next: SourceSlice<'a>) -> Self {
+        use SourceSlice::*;
+
+        match (self, next) {
+            (Detached, _) | (_, Detached) => Detached,
+            (Empty, slice) | (slice, Empty) => slice,
+            (Unanchored(length), Unanchored(next_length)) => Unanchored(length + next_length),
+            (Anchored { source, start, end }, Unanchored(length)) => Anchored {
+                source,
+                start,
+                end: end + length,
+            },
+            (Unanchored(length), Anchored { source, start, end }) if length <= start => Anchored {
+                source,
+                start: start - length,
+                end,
+            },
+            (
+                Anchored { source, start, end },
+                Anchored {
+                    source: next_source,
+                    start: next_start,
+                    end: next_end,
+                },
+            ) if Arc::ptr_eq(source, next_source) && end == next_start => Anchored {
+                source,
+                start,
+                end: next_end,
+            },
+            _ => Detached,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&'a str> {
+        match *self {
+            SourceSlice::Empty => Some(""),
+            SourceSlice::Anchored { source, start, end } => source.get(start..end),
+            SourceSlice::Unanchored(_) | SourceSlice::Detached => None,
+        }
+    }
+
+    pub fn text_or_else(&self, owned: impl FnOnce() -> String) -> Cow<'a, str> {
+        match self.as_str() {
+            Some(text) => Cow::Borrowed(text),
+            None => Cow::Owned(owned()),
+        }
+    }
+}
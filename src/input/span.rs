@@ -0,0 +1,95 @@
+use {
+    super::Position,
+    std::fmt::{self, Display, Formatter},
+};
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Span {
+    pub start: Position,
+    pub end: Position,
+}
+
+impl Span {
+    pub fn new(start: Position, end: Position) -> Self {
+        Span { start, end }
+    }
+
+    pub fn byte_range(&self) -> std::ops::Range<usize> {
+        self.start.byte_offset..self.end.byte_offset
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start.byte_offset == self.end.byte_offset
+    }
+}
+
+impl Display for Span {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "{}-{}", self.start, self.end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::Span,
+        crate::{
+            input::StrInput,
+            literals, parse_complete, ranges,
+            rules::{Literal, OneOrMore, Range},
+        },
+    };
+
+    literals!(Let: "let", Semi: ";", Space: " ");
+    ranges!(Lower: ['a', 'z']);
+
+    #[derive(Clone, Debug, pegast_derive::PegAstNode)]
+    struct Name {
+        letters: OneOrMore<Range<Lower>>,
+        #[pegast(span)]
+        span: Span,
+    }
+
+    #[derive(Clone, Debug, pegast_derive::PegAstNode)]
+    #[pegast(skip = "Literal<Space>")]
+    struct Declaration {
+        keyword: Literal<Let>,
+        name: Name,
+        semicolon: Literal<Semi>,
+        #[pegast(span)]
+        span: Span,
+    }
+
+    #[derive(Clone, Debug, pegast_derive::PegAstNode)]
+    #[pegast(skip = "Literal<Space>")]
+    struct Program {
+        declarations: Vec<Declaration>,
+    }
+
+    #[test]
+    fn span_fields_cover_the_consumed_input() {
+        let name = parse_complete::<Name>(&mut StrInput::new("abc")).unwrap();
+
+        assert_eq!(name.span.byte_range(), 0..3);
+        assert_eq!(name.span.end.column, 4);
+    }
+
+    #[test]
+    fn span_fields_start_after_skipped_trivia() {
+        let source = "let ab ;  let c;";
+        let program = parse_complete::<Program>(&mut StrInput::new(source)).unwrap();
+        let spans = program
+            .declarations
+            .iter()
+            .map(|declaration| declaration.span.byte_range())
+            .collect::<Vec<_>>();
+        let names = program
+            .declarations
+            .iter()
+            .map(|declaration| declaration.name.span.byte_range())
+            .collect::<Vec<_>>();
+
+        assert_eq!(spans, [0..8, 10..16]);
+        assert_eq!(names, [4..6, 14..15]);
+    }
+}
@@ -0,0 +1,327 @@
+use {
+    super::{
+        element::{chars_eq_ignore_case, decode_utf8},
+        Input, MemoTable, ParseState, Position, Span,
+    },
+    std::{borrow::BorrowMut, sync::Arc},
+};
+
+#[derive(Clone, Debug)]
+pub struct StrInput<'s, S = ParseState> {
+    source: &'s str,
+    shared_source: Option<&'s Arc<str>>,
+    position: Position,
+    state: S,
+}
+
+impl<'s> StrInput<'s> {
+    pub fn new(source: &'s str) -> Self {
+        StrInput {
+            source,
+            shared_source: None,
+            position: Position::new(),
+            state: ParseState::new(),
+        }
+    }
+
+    pub fn shared(source: &'s Arc<str>) -> Self {
+        StrInput {
+            source,
+            shared_source: Some(source),
+            position: Position::new(),
+            state: ParseState::new(),
+        }
+    }
+}
+
+impl<'s, S> StrInput<'s, S> {
+    pub fn source(&self) -> &'s str {
+        self.source
+    }
+
+    pub fn remaining(&self) -> &'s str {
+        let mut offset = self.position.byte_offset;
+
+        while !self.source.is_char_boundary(offset) {
+            offset += 1;
+        }
+
+        &self.source[offset..]
+    }
+
+    pub fn slice(&self, span: Span) -> &'s str {
+        &self.source[span.byte_range()]
+    }
+
+    fn remaining_bytes(&self) -> &'s [u8] {
+        &self.source.as_bytes()[self.position.byte_offset..]
+    }
+}
+
+impl<'s> From<&'s str> for StrInput<'s> {
+    fn from(source: &'s str) -> Self {
+        StrInput::new(source)
+    }
+}
+
+impl<'s, S> Input for StrInput<'s, S>
+where
+    S: BorrowMut<ParseState>,
+{
+    fn position(&self) -> Position {
+        self.position
+    }
+
+    fn advance(&mut self, amount: usize) {
+        for _ in 0..amount {
+            if self.next().is_none() {
+                break;
+            }
+        }
+    }
+
+    fn advance_to(&mut self, position: Position) {
+        assert!(
+            position >= self.position,
+            "Attempt to advance input backwards"
+        );
+
+        self.position = position;
+    }
+
+    fn check(&mut self, string: &str) -> bool {
+        self.remaining_bytes().starts_with(string.as_bytes())
+    }
+
+    fn consume(&mut self, string: &str) -> bool {
+        if self.check(string) {
+            for character in string.chars() {
+                self.position.advance(character);
+            }
+
+            true
+        } else {
+            false
+        }
+    }
+
+    fn check_ignore_case(&mut self, string: &str) -> bool {
+        if !self.source.is_char_boundary(self.position.byte_offset) {
+            return string.is_empty();
+        }
+
+        let mut remaining = self.remaining().chars();
+
+        string.chars().all(|expected| {
+            remaining
+                .next()
+                .map(|character| chars_eq_ignore_case(character, expected))
+                .unwrap_or(false)
+        })
+    }
+
+    fn consume_ignore_case(&mut self, string: &str) -> bool {
+        if self.check_ignore_case(string) {
+            self.advance(string.chars().count());
+
+            true
+        } else {
+            false
+        }
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let (character, length) = decode_utf8(self.remaining_bytes())?;
+
+        if character.len_utf8() == length {
+            self.position.advance(character);
+        } else {
+            self.next_byte();
+        }
+
+        Some(character)
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        decode_utf8(self.remaining_bytes()).map(|(character, _)| character)
+    }
+
+    fn check_bytes(&mut self, bytes: &[u8]) -> bool {
+        self.remaining_bytes().starts_with(bytes)
+    }
+
+    fn consume_bytes(&mut self, bytes: &[u8]) -> bool {
+        if self.check_bytes(bytes) {
+            for &byte in bytes {
+                self.position.advance_byte(byte);
+            }
+
+            true
+        } else {
+            false
+        }
+    }
+
+    fn next_byte(&mut self) -> Option<u8> {
+        let result = self.peek_byte();
+
+        if let Some(byte) = result {
+            self.position.advance_byte(byte);
+        }
+
+        result
+    }
+
+    fn peek_byte(&mut self) -> Option<u8> {
+        self.remaining_bytes().first().cloned()
+    }
+
+    fn shared_source(&self) -> Option<&Arc<str>> {
+        self.shared_source
+    }
+
+    fn memo_table(&mut self) -> Option<&mut MemoTable> {
+        None
+    }
+
+    fn state(&mut self) -> &mut ParseState {
+        self.state.borrow_mut()
+    }
+
+    fn peek_only<'a>(&'a mut self) -> Box<dyn Input + 'a> {
+        Box::new(StrInput {
+            source: self.source,
+            shared_source: self.shared_source,
+            position: self.position,
+            state: self.state.borrow_mut(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::StrInput,
+        crate::{
+            byte_literals,
+            input::Input,
+            literals, parse_complete, ranges,
+            rules::{Any, AnyByte, ByteLiteral, Literal, OneOrMore, Range, Spanned},
+            PegAstNode,
+        },
+        std::{borrow::Cow, sync::Arc},
+    };
+
+    literals!(Plus: "+", Space: " ");
+    byte_literals!(Accent: b"\xc3\xa9");
+    ranges!(Digit: ['0', '9']);
+
+    type Number = OneOrMore<Range<Digit>>;
+
+    #[derive(Clone, Debug, pegast_derive::PegAstNode)]
+    struct Sum {
+        left: Number,
+        plus: Literal<Plus>,
+        right: Number,
+    }
+
+    #[derive(Clone, Debug, pegast_derive::PegAstNode)]
+    #[pegast(skip = "Literal<Space>")]
+    struct SpacedSum {
+        left: Number,
+        plus: Literal<Plus>,
+        right: Vec<Range<Digit>>,
+        #[pegast(trivia)]
+        trivia: Vec<String>,
+    }
+
+    fn assert_borrowed_from(source: &Arc<str>, text: Cow<'_, str>, expected: &str) {
+        let source_range = source.as_bytes().as_ptr_range();
+
+        assert_eq!(text, expected);
+        assert!(matches!(text, Cow::Borrowed(_)));
+        assert!(source_range.contains(&text.as_ptr()));
+        assert!(text.as_ptr() as usize + text.len() <= source_range.end as usize);
+    }
+
+    #[test]
+    fn peeking_does_not_consume() {
+        let mut input = StrInput::new("12");
+
+        assert_eq!(input.peek_only().next(), Some('1'));
+        assert_eq!(input.position().byte_offset, 0);
+        assert_eq!(input.remaining(), "12");
+    }
+
+    #[test]
+    fn repetitions_borrow_from_a_shared_source() {
+        let source: Arc<str> = Arc::from("123");
+        let number = parse_complete::<Number>(&mut StrInput::shared(&source)).unwrap();
+
+        assert_borrowed_from(&source, number.parsed_string(), "123");
+
+        let source: Arc<str> = Arc::from("4567");
+        let digits = parse_complete::<Vec<Range<Digit>>>(&mut StrInput::shared(&source)).unwrap();
+
+        assert_borrowed_from(&source, digits.parsed_string(), "4567");
+    }
+
+    #[test]
+    fn sequences_with_literals_borrow_from_a_shared_source() {
+        let source: Arc<str> = Arc::from("12+34");
+        let tuple =
+            parse_complete::<(Number, Literal<Plus>, Number)>(&mut StrInput::shared(&source))
+                .unwrap();
+        let sum = parse_complete::<Sum>(&mut StrInput::shared(&source)).unwrap();
+
+        assert_borrowed_from(&source, tuple.parsed_string(), "12+34");
+        assert_borrowed_from(&source, sum.parsed_string(), "12+34");
+    }
+
+    #[test]
+    fn skipped_trivia_borrows_from_a_shared_source() {
+        let source: Arc<str> = Arc::from("12 + 3 4");
+        let sum = parse_complete::<SpacedSum>(&mut StrInput::shared(&source)).unwrap();
+
+        assert_borrowed_from(&source, sum.parsed_string(), "12 + 3 4");
+        assert_eq!(sum.parsed_string_length(), 8);
+    }
+
+    #[test]
+    fn unshared_sequences_own_their_text() {
+        let sum = parse_complete::<Sum>(&mut StrInput::new("12+34")).unwrap();
+
+        assert!(matches!(sum.parsed_string(), Cow::Owned(text) if text == "12+34"));
+    }
+
+    #[test]
+    fn spanned_tokens_borrow_from_the_source() {
+        let source = "123";
+        let mut input = StrInput::new(source);
+        let number = parse_complete::<Spanned<Number>>(&mut input).unwrap();
+        let text = input.slice(number.span());
+
+        assert_eq!(text, "123");
+        assert_eq!(text.as_ptr(), source.as_ptr());
+    }
+
+    #[test]
+    fn byte_rules_match_non_ascii_bytes() {
+        let mut input = StrInput::new("éa");
+        let (accent, letter) = parse_complete::<(ByteLiteral<Accent>, Any)>(&mut input).unwrap();
+
+        assert_eq!(accent.parsed_string(), "é");
+        assert_eq!(letter.parsed_string(), "a");
+    }
+
+    #[test]
+    fn characters_after_a_split_byte_are_replaced() {
+        let mut input = StrInput::new("éa");
+        let (first, rest, letter) = parse_complete::<(AnyByte, Any, Any)>(&mut input).unwrap();
+
+        assert_eq!(first.byte(), 0xc3);
+        assert_eq!(rest.parsed_string(), "\u{fffd}");
+        assert_eq!(letter.parsed_string(), "a");
+        assert_eq!(input.position().byte_offset, 3);
+    }
+}
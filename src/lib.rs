@@ -1,30 +1,51 @@
+pub mod cst;
+pub mod diagnostic;
+mod expected;
+pub mod grammar;
 pub mod input;
 pub mod rules;
 
+#[cfg(test)]
+extern crate self as pegast;
+
+pub use crate::expected::Expected;
+
+#[cfg(feature = "derive")]
+pub use {
+    crate::rules::sets::SetEntries,
+    pegast_derive::{grammar, FromStr, PegAstNode, SetEntries},
+};
 use {
-    crate::input::Input,
+    crate::{
+        diagnostic::Diagnostic,
+        grammar::{Grammar, GrammarNode},
+        input::{Input, Position, SourceSlice},
+        rules::EndOfInput,
+    },
     std::{
+        any::Any,
         borrow::Cow,
+        cmp::Ordering,
         error::Error,
         fmt::{self, Display, Formatter},
         ops::Deref,
     },
 };
-#[cfg(feature = "derive")]
-pub use {
-    crate::rules::sets::SetEntries,
-    pegast_derive::{FromStr, PegAstNode, SetEntries},
-};
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct ParseError {
-    pub expected: Vec<String>,
-    pub position: usize,
+    pub expected: Vec<Expected>,
+    pub position: Position,
+    pub cut: bool,
 }
 
 impl Display for ParseError {
     fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
-        writeln!(formatter, "Failed to parse input at {}", self.position)?;
+        writeln!(
+            formatter,
+            "Failed to parse input at line {}, column {}",
+            self.position.line, self.position.column
+        )?;
         write!(formatter, "  Expected:")?;
 
         match self.expected.len() {
@@ -43,22 +64,141 @@ impl Display for ParseError {
     }
 }
 
+impl ParseError {
+    pub fn new(expected: Vec<Expected>, position: Position) -> Self {
+        ParseError {
+            expected,
+            position,
+            cut: false,
+        }
+    }
+
+    pub fn cut_if(mut self, cut: bool) -> Self {
+        self.cut |= cut;
+        self
+    }
+
+    pub fn diagnostic<'a>(&'a self, source: &'a str) -> Diagnostic<'a> {
+        Diagnostic::new(source, self)
+    }
+
+    pub fn merge(mut self, other: ParseError) -> ParseError {
+        match self.position.cmp(&other.position) {
+            Ordering::Less => other,
+            Ordering::Greater => self,
+            Ordering::Equal => {
+                for expected in other.expected {
+                    if !self.expected.contains(&expected) {
+                        self.expected.push(expected);
+                    }
+                }
+
+                self
+            }
+        }
+    }
+}
+
 impl Error for ParseError {}
 
 pub trait PegAstNode: Sized {
+    const CUT: bool = false;
+
     fn parse(input: &mut impl Input) -> Result<Self, ParseError>;
     fn parsed_string(&self) -> Cow<'_, str>;
-    fn expecting() -> Vec<String>;
+    fn expecting() -> Vec<Expected>;
 
     fn parsed_string_length(&self) -> usize {
         self.parsed_string().len()
     }
+
+    fn source_slice(&self) -> SourceSlice<'_> {
+        SourceSlice::Detached
+    }
+
+    fn grammar_node(_grammar: &mut Grammar) -> GrammarNode {
+        GrammarNode::Named(std::any::type_name::<Self>())
+    }
+
+    fn parse_skipping<Trivia: PegAstNode>(
+        input: &mut impl Input,
+        _trivia: &mut Vec<String>,
+    ) -> Result<Self, ParseError> {
+        Self::parse(input)
+    }
+
+    fn push_skipped<'t>(
+        &self,
+        string: &mut String,
+        _trivia: &mut impl Iterator<Item = &'t String>,
+    ) {
+        string.push_str(&self.parsed_string());
+    }
+
+    fn skipped_source_slice<'a>(
+        &'a self,
+        _trivia: &mut impl Iterator<Item = &'a String>,
+    ) -> SourceSlice<'a> {
+        self.source_slice()
+    }
+
+    fn skipping_grammar_node<Trivia: PegAstNode>(grammar: &mut Grammar) -> GrammarNode {
+        Self::grammar_node(grammar)
+    }
+
+    // Visits this node and then the nodes nested in it, in source order.
+    fn visit_nodes<'node>(&'node self, visitor: &mut dyn FnMut(&'node dyn Any))
+    where
+        Self: 'static,
+    {
+        visitor(self);
+    }
+}
+
+pub fn parse_complete<T>(input: &mut impl Input) -> Result<T, ParseError>
+where
+    T: PegAstNode,
+{
+    let node = T::parse(input).map_err(|error| input.state().fail(error))?;
+
+    EndOfInput::parse(input).map_err(|error| input.state().fail(error))?;
+
+    Ok(node)
+}
+
+pub fn parse_recovering<T>(input: &mut impl Input) -> (Option<T>, Vec<ParseError>)
+where
+    T: PegAstNode,
+{
+    input.state().enable_recovery();
+
+    let result = T::parse(input).map_err(|error| input.state().fail(error));
+    let trailing = match result {
+        Ok(_) => EndOfInput::parse(input)
+            .map_err(|error| input.state().fail(error))
+            .err(),
+        Err(_) => None,
+    };
+    let mut errors = input.state().take_errors();
+
+    match result {
+        Ok(node) => {
+            errors.extend(trailing);
+            (Some(node), errors)
+        }
+        Err(error) => {
+            errors.push(error);
+            (None, errors)
+        }
+    }
 }
 
 impl<T> PegAstNode for Box<T>
 where
     T: PegAstNode,
 {
+    const CUT: bool = T::CUT;
+
     fn parse(input: &mut impl Input) -> Result<Self, ParseError> {
         let inner = T::parse(input)?;
 
@@ -73,7 +213,132 @@ where
         self.deref().parsed_string_length()
     }
 
-    fn expecting() -> Vec<String> {
+    fn source_slice(&self) -> SourceSlice<'_> {
+        self.deref().source_slice()
+    }
+
+    fn expecting() -> Vec<Expected> {
         T::expecting()
     }
+
+    fn grammar_node(grammar: &mut Grammar) -> GrammarNode {
+        T::grammar_node(grammar)
+    }
+
+    fn parse_skipping<Trivia: PegAstNode>(
+        input: &mut impl Input,
+        trivia: &mut Vec<String>,
+    ) -> Result<Self, ParseError> {
+        let inner = T::parse_skipping::<Trivia>(input, trivia)?;
+
+        Ok(Box::new(inner))
+    }
+
+    fn push_skipped<'t>(&self, string: &mut String, trivia: &mut impl Iterator<Item = &'t String>) {
+        self.deref().push_skipped(string, trivia)
+    }
+
+    fn skipped_source_slice<'a>(
+        &'a self,
+        trivia: &mut impl Iterator<Item = &'a String>,
+    ) -> SourceSlice<'a> {
+        self.deref().skipped_source_slice(trivia)
+    }
+
+    fn skipping_grammar_node<Trivia: PegAstNode>(grammar: &mut Grammar) -> GrammarNode {
+        T::skipping_grammar_node::<Trivia>(grammar)
+    }
+
+    fn visit_nodes<'node>(&'node self, visitor: &mut dyn FnMut(&'node dyn Any))
+    where
+        Self: 'static,
+    {
+        self.deref().visit_nodes(visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        input::{Position, StrInput},
+        literals, parse_complete, ranges,
+        rules::{Literal, OneOrMore, Range},
+        Expected, ParseError,
+    };
+
+    literals!(Equals: "=", Open: "(", Close: ")");
+    ranges!(Lower: ['a', 'z'], Digit: ['0', '9']);
+
+    type Name = OneOrMore<Range<Lower>>;
+
+    #[derive(Clone, Debug, pegast_derive::PegAstNode)]
+    enum Statement {
+        Assignment(Name, Literal<Equals>, OneOrMore<Range<Digit>>),
+        Call(Name, Literal<Open>, Literal<Close>),
+    }
+
+    pegast_derive::grammar! {
+        Sum = Number ("+" Number)*;
+        Number = '0'..='9'+;
+    }
+
+    pegast_derive::grammar! {
+        Product = Factor ("*" Factor)*;
+        Factor = '0'..='9'+ / "(" Product ")";
+    }
+
+    #[test]
+    fn grammar_macro_does_not_require_trait_imports() {
+        let sum = parse_complete::<Sum>(&mut StrInput::new("1+23")).unwrap();
+
+        assert_eq!(crate::PegAstNode::parsed_string(&sum), "1+23");
+    }
+
+    #[test]
+    fn grammar_macro_blocks_share_a_module() {
+        let product = parse_complete::<Product>(&mut StrInput::new("(1*2)*34")).unwrap();
+
+        assert_eq!(crate::PegAstNode::parsed_string(&product), "(1*2)*34");
+    }
+
+    fn offset(byte_offset: usize) -> Position {
+        Position {
+            byte_offset,
+            char_offset: byte_offset,
+            line: 1,
+            column: byte_offset + 1,
+        }
+    }
+
+    #[test]
+    fn merges_failures_of_alternatives_at_the_furthest_position() {
+        let error = parse_complete::<Statement>(&mut StrInput::new("ab?")).unwrap_err();
+
+        assert_eq!(error.position, offset(2));
+        assert_eq!(
+            error.expected,
+            [
+                Expected::CharRange('a', 'z'),
+                Expected::Literal("="),
+                Expected::Literal("("),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_only_the_alternative_that_got_furthest() {
+        let error = parse_complete::<Statement>(&mut StrInput::new("ab=?")).unwrap_err();
+
+        assert_eq!(error.position, offset(3));
+        assert_eq!(error.expected, [Expected::CharRange('0', '9')]);
+    }
+
+    #[test]
+    fn merging_keeps_the_furthest_error() {
+        let near = ParseError::new(vec![Expected::Literal("=")], offset(1));
+        let far = ParseError::new(vec![Expected::Literal("(")], offset(2));
+
+        assert_eq!(near.clone().merge(far.clone()).expected, far.expected);
+        assert_eq!(far.clone().merge(near).position, far.position);
+    }
 }
@@ -1,6 +1,10 @@
 use {
-    crate::{input::Input, ParseError, PegAstNode},
-    std::borrow::Cow,
+    crate::{
+        grammar::{Grammar, GrammarNode},
+        input::{Input, SourceSlice},
+        Expected, ParseError, PegAstNode,
+    },
+    std::{any::Any, borrow::Cow},
 };
 
 macro_rules! tuple_impl {
@@ -14,13 +18,26 @@ macro_rules! tuple_impl {
             $first_type: PegAstNode,
             $( $type: PegAstNode, )*
         {
+            #[allow(unused_assignments, unused_mut, unused_variables)]
             fn parse(input: &mut impl Input) -> Result<Self, ParseError> {
                 let (new_position, tuple) = {
                     let mut peek_input = input.peek_only();
-                    let $first_binding = $first_type::parse(&mut peek_input)?;
-                    $( let $binding = $type::parse(&mut peek_input)?; )*
+                    let start = peek_input.position();
+                    peek_input.state().mark_boundary(start);
+                    let $first_binding = $first_type::parse(&mut peek_input)
+                        .map_err(|error| peek_input.state().fail(error))?;
+                    let mut cut = $first_type::CUT;
+                    $(
+                        let boundary = peek_input.position();
+                        peek_input.state().mark_boundary(boundary);
+                        let $binding = $type::parse(&mut peek_input)
+                            .map_err(|error| peek_input.state().fail(error).cut_if(cut))?;
+                        cut |= $type::CUT;
+                    )*
+                    let end = peek_input.position();
+                    peek_input.state().mark_boundary(end);
 
-                    (peek_input.position(), ($first_binding, $( $binding ),*))
+                    (end, ($first_binding, $( $binding ),*))
                 };
 
                 input.advance_to(new_position);
@@ -29,11 +46,13 @@ macro_rules! tuple_impl {
             }
 
             fn parsed_string(&self) -> Cow<'_, str> {
-                let mut string = String::new();
+                self.source_slice().text_or_else(|| {
+                    let mut string = String::new();
 
-                $( string.push_str(self.$field.parsed_string().as_ref()); )*
+                    $( string.push_str(self.$field.parsed_string().as_ref()); )*
 
-                Cow::Owned(string)
+                    string
+                })
             }
 
             fn parsed_string_length(&self) -> usize {
@@ -44,9 +63,28 @@ macro_rules! tuple_impl {
                 sum
             }
 
-            fn expecting() -> Vec<String> {
+            fn source_slice(&self) -> SourceSlice<'_> {
+                SourceSlice::Empty $( .then(self.$field.source_slice()) )*
+            }
+
+            fn expecting() -> Vec<Expected> {
                 $first_type::expecting()
             }
+
+            fn grammar_node(grammar: &mut Grammar) -> GrammarNode {
+                GrammarNode::Sequence(vec![
+                    $first_type::grammar_node(grammar),
+                    $( $type::grammar_node(grammar), )*
+                ])
+            }
+
+            fn visit_nodes<'node>(&'node self, visitor: &mut dyn FnMut(&'node dyn Any))
+            where
+                Self: 'static,
+            {
+                visitor(self);
+                $( self.$field.visit_nodes(visitor); )*
+            }
         }
     };
 }
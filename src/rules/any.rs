@@ -1,34 +1,51 @@
 use {
-    crate::{input::Input, ParseError, PegAstNode},
-    std::{borrow::Cow, iter},
+    super::encoded_char::SourceChar,
+    crate::{
+        grammar::{Grammar, GrammarNode},
+        input::{Input, SourceSlice},
+        Expected, ParseError, PegAstNode,
+    },
+    std::borrow::Cow,
 };
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-pub struct Any(char);
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Any(SourceChar);
 
 impl From<char> for Any {
     fn from(character: char) -> Self {
-        Any(character)
+        Any(SourceChar::from(character))
     }
 }
 
 impl PegAstNode for Any {
     fn parse(input: &mut impl Input) -> Result<Self, ParseError> {
-        input.next().map(Any).ok_or_else(|| ParseError {
-            expected: Self::expecting(),
-            position: input.position(),
-        })
+        let start = input.position();
+        let character = SourceChar::next_from(input)
+            .ok_or_else(|| ParseError::new(Self::expecting(), start))?;
+        let end = input.position();
+
+        input.state().mark_token(start, end);
+
+        Ok(Any(character))
     }
 
     fn parsed_string(&self) -> Cow<'_, str> {
-        Cow::Owned(iter::once(self.0).collect())
+        Cow::Borrowed(self.0.as_str())
     }
 
     fn parsed_string_length(&self) -> usize {
-        1
+        self.0.as_str().len()
+    }
+
+    fn source_slice(&self) -> SourceSlice<'_> {
+        self.0.source_slice()
+    }
+
+    fn expecting() -> Vec<Expected> {
+        vec![Expected::AnyChar]
     }
 
-    fn expecting() -> Vec<String> {
-        vec!["any character".to_owned()]
+    fn grammar_node(_grammar: &mut Grammar) -> GrammarNode {
+        GrammarNode::AnyChar
     }
 }
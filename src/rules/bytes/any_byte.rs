@@ -0,0 +1,54 @@
+use {
+    super::bytes_as_str,
+    crate::{
+        grammar::{Grammar, GrammarNode},
+        input::Input,
+        Expected, ParseError, PegAstNode,
+    },
+    std::{borrow::Cow, slice},
+};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AnyByte(u8);
+
+impl AnyByte {
+    pub fn byte(&self) -> u8 {
+        self.0
+    }
+}
+
+impl From<u8> for AnyByte {
+    fn from(byte: u8) -> Self {
+        AnyByte(byte)
+    }
+}
+
+impl PegAstNode for AnyByte {
+    fn parse(input: &mut impl Input) -> Result<Self, ParseError> {
+        let start = input.position();
+        let byte = input
+            .next_byte()
+            .ok_or_else(|| ParseError::new(Self::expecting(), start))?;
+        let end = input.position();
+
+        input.state().mark_token(start, end);
+
+        Ok(AnyByte(byte))
+    }
+
+    fn parsed_string(&self) -> Cow<'_, str> {
+        bytes_as_str(slice::from_ref(&self.0))
+    }
+
+    fn parsed_string_length(&self) -> usize {
+        1
+    }
+
+    fn expecting() -> Vec<Expected> {
+        vec![Expected::AnyByte]
+    }
+
+    fn grammar_node(_grammar: &mut Grammar) -> GrammarNode {
+        GrammarNode::AnyByte
+    }
+}
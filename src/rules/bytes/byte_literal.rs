@@ -0,0 +1,93 @@
+use {
+    super::bytes_as_str,
+    crate::{
+        grammar::{Grammar, GrammarNode},
+        input::Input,
+        Expected, ParseError, PegAstNode,
+    },
+    std::{borrow::Cow, marker::PhantomData},
+};
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ByteLiteral<V: ByteLiteralValue>(PhantomData<V>);
+
+impl<V: ByteLiteralValue> ByteLiteral<V> {
+    pub fn new() -> Self {
+        ByteLiteral(PhantomData)
+    }
+
+    pub fn bytes(&self) -> &'static [u8] {
+        V::LITERAL
+    }
+}
+
+impl<V: ByteLiteralValue> PegAstNode for ByteLiteral<V> {
+    fn parse(input: &mut impl Input) -> Result<Self, ParseError> {
+        let start = input.position();
+
+        if input.consume_bytes(V::LITERAL) {
+            let end = input.position();
+
+            input.state().mark_token(start, end);
+
+            Ok(ByteLiteral(PhantomData))
+        } else {
+            Err(ParseError::new(Self::expecting(), input.position()))
+        }
+    }
+
+    fn parsed_string(&self) -> Cow<'_, str> {
+        bytes_as_str(V::LITERAL)
+    }
+
+    fn parsed_string_length(&self) -> usize {
+        V::LITERAL.len()
+    }
+
+    fn expecting() -> Vec<Expected> {
+        vec![Expected::Bytes(V::LITERAL)]
+    }
+
+    fn grammar_node(_grammar: &mut Grammar) -> GrammarNode {
+        GrammarNode::Bytes(V::LITERAL)
+    }
+}
+
+pub trait ByteLiteralValue {
+    const LITERAL: &'static [u8];
+}
+
+#[macro_export]
+macro_rules! byte_literals {
+    ( $name:ident : $literal:expr ) => {
+        #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+        enum $name {}
+
+        byte_literals!(@impl $name : $literal);
+    };
+
+    ( pub $name:ident : $literal:expr ) => {
+        #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+        pub enum $name {}
+
+        byte_literals!(@impl $name : $literal);
+    };
+
+    ( $name:ident : $literal:expr , $( $rest:tt )* ) => {
+        byte_literals!($name : $literal);
+        byte_literals!($( $rest )*);
+    };
+
+    ( pub $name:ident : $literal:expr , $( $rest:tt )* ) => {
+        byte_literals!(pub $name : $literal);
+        byte_literals!($( $rest )*);
+    };
+
+    ( @impl $name:ident : $literal:expr ) => {
+        impl pegast::rules::bytes::byte_literal::ByteLiteralValue for $name {
+            const LITERAL: &'static [u8] = $literal;
+        }
+    };
+
+    ( $(,)* ) => {};
+}
@@ -0,0 +1,130 @@
+use {
+    super::bytes_as_str,
+    crate::{
+        grammar::{Grammar, GrammarNode},
+        input::Input,
+        Expected, ParseError, PegAstNode,
+    },
+    std::{
+        borrow::Cow,
+        convert::TryFrom,
+        error::Error,
+        fmt::{self, Debug, Display, Formatter},
+        marker::PhantomData,
+        slice,
+    },
+};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct OutOfByteRangeError<V: ByteRangeValue>(u8, PhantomData<V>);
+
+impl<V: ByteRangeValue> Display for OutOfByteRangeError<V> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "input byte {:#04x} is out of the range [{:#04x}-{:#04x}]",
+            self.0,
+            V::START,
+            V::END
+        )
+    }
+}
+
+impl<V: ByteRangeValue> Error for OutOfByteRangeError<V> {}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ByteRange<V: ByteRangeValue>(u8, PhantomData<V>);
+
+impl<V: ByteRangeValue> ByteRange<V> {
+    pub fn byte(&self) -> u8 {
+        self.0
+    }
+}
+
+impl<V: ByteRangeValue> TryFrom<u8> for ByteRange<V> {
+    type Error = OutOfByteRangeError<V>;
+
+    fn try_from(byte: u8) -> Result<Self, Self::Error> {
+        if byte >= V::START && byte <= V::END {
+            Ok(ByteRange(byte, PhantomData))
+        } else {
+            Err(OutOfByteRangeError(byte, PhantomData))
+        }
+    }
+}
+
+impl<V: ByteRangeValue> PegAstNode for ByteRange<V> {
+    fn parse(input: &mut impl Input) -> Result<Self, ParseError> {
+        let start = input.position();
+
+        if let Some(input_byte) = input.peek_byte() {
+            if let Ok(result) = Self::try_from(input_byte) {
+                let _ = input.next_byte();
+                let end = input.position();
+
+                input.state().mark_token(start, end);
+
+                return Ok(result);
+            }
+        }
+
+        Err(ParseError::new(Self::expecting(), input.position()))
+    }
+
+    fn parsed_string(&self) -> Cow<'_, str> {
+        bytes_as_str(slice::from_ref(&self.0))
+    }
+
+    fn parsed_string_length(&self) -> usize {
+        1
+    }
+
+    fn expecting() -> Vec<Expected> {
+        vec![Expected::ByteRange(V::START, V::END)]
+    }
+
+    fn grammar_node(_grammar: &mut Grammar) -> GrammarNode {
+        GrammarNode::ByteRange(V::START, V::END)
+    }
+}
+
+pub trait ByteRangeValue: Debug {
+    const START: u8;
+    const END: u8;
+}
+
+#[macro_export]
+macro_rules! byte_ranges {
+    ( $name:ident : [ $start:expr , $end:expr ] ) => {
+        #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+        enum $name {}
+
+        byte_ranges!(@impl $name : [ $start, $end ]);
+    };
+
+    ( pub $name:ident : [ $start:expr , $end:expr ] ) => {
+        #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+        pub enum $name {}
+
+        byte_ranges!(@impl $name : [ $start, $end ]);
+    };
+
+    ( $name:ident : [ $start:expr , $end:expr ] , $( $rest:tt )* ) => {
+        byte_ranges!($name : [ $start, $end ]);
+        byte_ranges!($( $rest )*);
+    };
+
+    ( pub $name:ident : [ $start:expr , $end:expr ] , $( $rest:tt )* ) => {
+        byte_ranges!(pub $name : [ $start, $end ]);
+        byte_ranges!($( $rest )*);
+    };
+
+    ( @impl $name:ident : [ $start:expr , $end:expr ] ) => {
+        impl pegast::rules::bytes::byte_range::ByteRangeValue for $name {
+            const START: u8 = $start;
+            const END: u8 = $end;
+        }
+    };
+
+    ( $(,)* ) => {};
+}
@@ -0,0 +1,14 @@
+mod any_byte;
+pub mod byte_literal;
+pub mod byte_range;
+
+pub use self::{any_byte::AnyByte, byte_literal::ByteLiteral, byte_range::ByteRange};
+
+use std::borrow::Cow;
+
+// Byte nodes decode their own bytes as UTF-8 and replace invalid sequences with U+FFFD, so a
+// character split across several byte nodes is not reassembled; use `byte()` or `bytes()` for
+// the raw input. Their lengths still count the input bytes they consumed.
+fn bytes_as_str(bytes: &[u8]) -> Cow<'_, str> {
+    String::from_utf8_lossy(bytes)
+}
@@ -0,0 +1,183 @@
+use {
+    super::encoded_char::SourceChar,
+    crate::{
+        grammar::{Grammar, GrammarNode},
+        input::{Input, SourceSlice},
+        Expected, ParseError, PegAstNode,
+    },
+    std::{
+        borrow::Cow,
+        convert::TryFrom,
+        error::Error,
+        fmt::{self, Debug, Display, Formatter},
+        marker::PhantomData,
+    },
+};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct NotInCharClassError<V: CharClassValue>(char, PhantomData<V>);
+
+impl<V: CharClassValue> Display for NotInCharClassError<V> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "input character '{}' is not {}",
+            self.0,
+            Expected::CharClass(V::RANGES, V::NEGATED)
+        )
+    }
+}
+
+impl<V: CharClassValue> Error for NotInCharClassError<V> {}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CharClass<V: CharClassValue>(SourceChar, PhantomData<V>);
+
+impl<V: CharClassValue> TryFrom<char> for CharClass<V> {
+    type Error = NotInCharClassError<V>;
+
+    fn try_from(character: char) -> Result<Self, Self::Error> {
+        if V::contains(character) {
+            Ok(CharClass(SourceChar::from(character), PhantomData))
+        } else {
+            Err(NotInCharClassError(character, PhantomData))
+        }
+    }
+}
+
+impl<V: CharClassValue> PegAstNode for CharClass<V> {
+    fn parse(input: &mut impl Input) -> Result<Self, ParseError> {
+        let start = input.position();
+
+        if let Some(input_char) = input.peek() {
+            if Self::try_from(input_char).is_ok() {
+                let character = SourceChar::next_from(input).expect("Peeked character is missing");
+                let end = input.position();
+
+                input.state().mark_token(start, end);
+
+                return Ok(CharClass(character, PhantomData));
+            }
+        }
+
+        Err(ParseError::new(Self::expecting(), input.position()))
+    }
+
+    fn parsed_string(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self.0.as_str())
+    }
+
+    fn parsed_string_length(&self) -> usize {
+        self.0.as_str().len()
+    }
+
+    fn source_slice(&self) -> SourceSlice<'_> {
+        self.0.source_slice()
+    }
+
+    fn expecting() -> Vec<Expected> {
+        vec![Expected::CharClass(V::RANGES, V::NEGATED)]
+    }
+
+    fn grammar_node(_grammar: &mut Grammar) -> GrammarNode {
+        GrammarNode::CharClass(V::RANGES, V::NEGATED)
+    }
+}
+
+pub trait CharClassValue: Debug {
+    const RANGES: &'static [(char, char)];
+    const NEGATED: bool;
+
+    fn contains(character: char) -> bool {
+        let in_ranges = Self::RANGES
+            .iter()
+            .any(|&(start, end)| character >= start && character <= end);
+
+        in_ranges != Self::NEGATED
+    }
+}
+
+#[macro_export]
+macro_rules! char_class {
+    (
+        $visibility:vis $name:ident : ^ $( $start:literal $( ..= $end:literal )? ),+ $(,)?
+        $( ; $( $rest:tt )* )?
+    ) => {
+        char_class!(@impl $visibility $name, true, $( $start $( ..= $end )? ),+);
+        $( char_class!($( $rest )*); )?
+    };
+
+    (
+        $visibility:vis $name:ident : $( $start:literal $( ..= $end:literal )? ),+ $(,)?
+        $( ; $( $rest:tt )* )?
+    ) => {
+        char_class!(@impl $visibility $name, false, $( $start $( ..= $end )? ),+);
+        $( char_class!($( $rest )*); )?
+    };
+
+    ( @impl $visibility:vis $name:ident, $negated:expr, $( $start:literal $( ..= $end:literal )? ),+ ) => {
+        #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+        $visibility enum $name {}
+
+        impl pegast::rules::char_class::CharClassValue for $name {
+            const RANGES: &'static [(char, char)] = &[
+                $( char_class!(@range $start $(, $end)?) ),+
+            ];
+            const NEGATED: bool = $negated;
+        }
+    };
+
+    ( @range $single:literal ) => {
+        ($single, $single)
+    };
+
+    ( @range $start:literal, $end:literal ) => {
+        ($start, $end)
+    };
+
+    ( $(;)* ) => {};
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{CharClass, CharClassValue},
+        crate::{input::StrInput, parse_complete, rules::OneOrMore, Expected, PegAstNode},
+        std::convert::TryFrom,
+    };
+
+    char_class! {
+        Identifier: 'a'..='z', 'A'..='Z', '_';
+        NotQuote: ^ '"', '\\'
+    }
+
+    #[test]
+    fn matches_ranges_and_single_characters() {
+        assert!(Identifier::contains('q'));
+        assert!(Identifier::contains('Z'));
+        assert!(Identifier::contains('_'));
+        assert!(!Identifier::contains('1'));
+        assert!(CharClass::<Identifier>::try_from('-').is_err());
+
+        let word =
+            parse_complete::<OneOrMore<CharClass<Identifier>>>(&mut StrInput::new("snake_Case"))
+                .unwrap();
+
+        assert_eq!(word.parsed_string(), "snake_Case");
+    }
+
+    #[test]
+    fn negated_classes_match_everything_else() {
+        assert!(NotQuote::contains('a'));
+        assert!(NotQuote::contains('é'));
+        assert!(!NotQuote::contains('"'));
+        assert!(!NotQuote::contains('\\'));
+
+        let error = CharClass::<NotQuote>::parse(&mut StrInput::new("\"")).unwrap_err();
+
+        assert_eq!(
+            error.expected,
+            [Expected::CharClass(&[('"', '"'), ('\\', '\\')], true)]
+        );
+    }
+}
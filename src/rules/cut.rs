@@ -0,0 +1,128 @@
+use {
+    crate::{
+        grammar::{Grammar, GrammarNode},
+        input::{Input, SourceSlice},
+        Expected, ParseError, PegAstNode,
+    },
+    std::borrow::Cow,
+};
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Cut;
+
+impl PegAstNode for Cut {
+    const CUT: bool = true;
+
+    fn parse(_input: &mut impl Input) -> Result<Self, ParseError> {
+        Ok(Cut)
+    }
+
+    fn parsed_string(&self) -> Cow<'_, str> {
+        Cow::Borrowed("")
+    }
+
+    fn parsed_string_length(&self) -> usize {
+        0
+    }
+
+    fn source_slice(&self) -> SourceSlice<'_> {
+        SourceSlice::Empty
+    }
+
+    fn expecting() -> Vec<Expected> {
+        Vec::new()
+    }
+
+    fn grammar_node(_grammar: &mut Grammar) -> GrammarNode {
+        GrammarNode::empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::Cut,
+        crate::{
+            input::StrInput,
+            literals, parse_complete, ranges,
+            rules::{Literal, Not, OneOrMore, Peek, Range},
+            Expected, PegAstNode,
+        },
+    };
+
+    literals!(If: "if", Open: "(", Close: ")");
+    ranges!(Lower: ['a', 'z']);
+
+    #[derive(Clone, Debug, pegast_derive::PegAstNode)]
+    enum Statement {
+        Conditional(
+            #[pegast(cut)] Literal<If>,
+            Literal<Open>,
+            OneOrMore<Range<Lower>>,
+            Literal<Close>,
+        ),
+        Word(OneOrMore<Range<Lower>>),
+    }
+
+    #[test]
+    fn backtracks_without_cut() {
+        let mut input = StrInput::new("if");
+        let result = parse_complete::<(
+            Option<(Literal<If>, Literal<Open>)>,
+            OneOrMore<Range<Lower>>,
+        )>(&mut input);
+
+        assert!(result.unwrap().0.is_none());
+    }
+
+    #[test]
+    fn cut_prevents_backtracking_out_of_optional() {
+        let mut input = StrInput::new("if");
+        let error = parse_complete::<(
+            Option<(Literal<If>, Cut, Literal<Open>)>,
+            OneOrMore<Range<Lower>>,
+        )>(&mut input)
+        .unwrap_err();
+
+        assert!(error.cut);
+        assert_eq!(error.position.byte_offset, 2);
+        assert_eq!(error.expected, vec![Expected::Literal("(")]);
+    }
+
+    #[test]
+    fn cut_field_commits_to_variant() {
+        let word = parse_complete::<Statement>(&mut StrInput::new("word")).unwrap();
+
+        assert!(matches!(word, Statement::Word(_)));
+
+        let error = parse_complete::<Statement>(&mut StrInput::new("if x")).unwrap_err();
+
+        assert!(error.cut);
+        assert_eq!(error.position.byte_offset, 2);
+    }
+
+    #[test]
+    fn cut_field_keeps_committed_variant() {
+        let statement = parse_complete::<Statement>(&mut StrInput::new("if(x)")).unwrap();
+
+        assert!(matches!(statement, Statement::Conditional(..)));
+        assert_eq!(statement.parsed_string(), "if(x)");
+    }
+
+    #[test]
+    fn lookaheads_do_not_propagate_cut() {
+        let mut input = StrInput::new("if");
+        let (peeked, word) =
+            parse_complete::<(Option<Peek<Statement>>, OneOrMore<Range<Lower>>)>(&mut input)
+                .unwrap();
+
+        assert!(peeked.is_none());
+        assert_eq!(word.parsed_string(), "if");
+
+        let mut input = StrInput::new("if");
+        let (_, word) =
+            parse_complete::<(Not<Statement>, OneOrMore<Range<Lower>>)>(&mut input).unwrap();
+
+        assert_eq!(word.parsed_string(), "if");
+    }
+}
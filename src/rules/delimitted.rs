@@ -1,6 +1,11 @@
 use {
-    crate::{input::Input, ParseError, PegAstNode},
-    std::{borrow::Cow, iter},
+    super::skipping::{skip_trivia, skipped_trivia_slice},
+    crate::{
+        grammar::{Grammar, GrammarNode},
+        input::{Input, SourceSlice},
+        Expected, ParseError, PegAstNode,
+    },
+    std::{any::Any, borrow::Cow, iter},
 };
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -71,7 +76,7 @@ where
     fn parse(input: &mut impl Input) -> Result<Self, ParseError> {
         let (new_position, result) = {
             let mut peek_input = input.peek_only();
-            let head = T::parse(&mut peek_input)?;
+            let head = T::parse(&mut peek_input).map_err(|error| peek_input.state().fail(error))?;
             let tail = Vec::parse(&mut peek_input)?;
 
             (peek_input.position(), Delimitted { head, tail })
@@ -86,13 +91,15 @@ where
         if self.tail.is_empty() {
             self.head.parsed_string()
         } else {
-            let mut string = self.head.parsed_string().into_owned();
+            self.source_slice().text_or_else(|| {
+                let mut string = self.head.parsed_string().into_owned();
 
-            for item in &self.tail {
-                string.push_str(&item.parsed_string());
-            }
+                for item in &self.tail {
+                    string.push_str(&item.parsed_string());
+                }
 
-            Cow::Owned(string)
+                string
+            })
         }
     }
 
@@ -100,9 +107,123 @@ where
         self.head.parsed_string_length() + self.tail.parsed_string_length()
     }
 
-    fn expecting() -> Vec<String> {
+    fn source_slice(&self) -> SourceSlice<'_> {
+        self.head.source_slice().then(self.tail.source_slice())
+    }
+
+    fn expecting() -> Vec<Expected> {
         T::expecting()
     }
+
+    fn grammar_node(grammar: &mut Grammar) -> GrammarNode {
+        GrammarNode::sequence(vec![
+            T::grammar_node(grammar),
+            Vec::<(D, T)>::grammar_node(grammar),
+        ])
+    }
+
+    fn parse_skipping<Trivia: PegAstNode>(
+        input: &mut impl Input,
+        trivia: &mut Vec<String>,
+    ) -> Result<Self, ParseError> {
+        let head = T::parse(input).map_err(|error| input.state().fail(error))?;
+        let mut tail = Vec::new();
+
+        loop {
+            let position = input.position();
+            let checkpoint = input.state().checkpoint();
+            let attempt = {
+                let mut peek_input = input.peek_only();
+                let before_delimitter = skip_trivia::<Trivia>(&mut peek_input)?;
+
+                D::parse(&mut peek_input).and_then(|delimitter| {
+                    let after_delimitter = skip_trivia::<Trivia>(&mut peek_input)?;
+                    let item = T::parse(&mut peek_input)?;
+
+                    Ok((
+                        peek_input.position(),
+                        [before_delimitter, after_delimitter],
+                        (delimitter, item),
+                    ))
+                })
+            };
+
+            match attempt {
+                Ok((end, skipped, pair)) => {
+                    input.advance_to(end);
+                    trivia.extend(skipped);
+                    tail.push(pair);
+
+                    if input.position() == position {
+                        break;
+                    }
+                }
+                Err(error) if error.cut => return Err(error),
+                Err(error) => {
+                    input.state().rollback(checkpoint);
+                    input.state().record_failure(error);
+                    break;
+                }
+            }
+        }
+
+        Ok(Delimitted { head, tail })
+    }
+
+    fn push_skipped<'t>(&self, string: &mut String, trivia: &mut impl Iterator<Item = &'t String>) {
+        string.push_str(&self.head.parsed_string());
+
+        for (delimitter, item) in &self.tail {
+            string.extend(trivia.next().map(String::as_str));
+            string.push_str(&delimitter.parsed_string());
+            string.extend(trivia.next().map(String::as_str));
+            string.push_str(&item.parsed_string());
+        }
+    }
+
+    fn skipped_source_slice<'a>(
+        &'a self,
+        trivia: &mut impl Iterator<Item = &'a String>,
+    ) -> SourceSlice<'a> {
+        let mut slice = self.head.source_slice();
+
+        for (delimitter, item) in &self.tail {
+            slice = slice
+                .then(skipped_trivia_slice(trivia))
+                .then(delimitter.source_slice())
+                .then(skipped_trivia_slice(trivia))
+                .then(item.source_slice());
+        }
+
+        slice
+    }
+
+    fn skipping_grammar_node<Trivia: PegAstNode>(grammar: &mut Grammar) -> GrammarNode {
+        let trivia = Vec::<Trivia>::grammar_node(grammar);
+
+        GrammarNode::sequence(vec![
+            T::grammar_node(grammar),
+            GrammarNode::zero_or_more(GrammarNode::sequence(vec![
+                trivia.clone(),
+                D::grammar_node(grammar),
+                trivia,
+                T::grammar_node(grammar),
+            ])),
+        ])
+    }
+
+    fn visit_nodes<'node>(&'node self, visitor: &mut dyn FnMut(&'node dyn Any))
+    where
+        Self: 'static,
+    {
+        visitor(self);
+        self.head.visit_nodes(visitor);
+
+        for (delimitter, item) in &self.tail {
+            delimitter.visit_nodes(visitor);
+            item.visit_nodes(visitor);
+        }
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -0,0 +1,86 @@
+use {
+    crate::input::{Input, SourceSlice},
+    std::{str, sync::Arc},
+};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct EncodedChar {
+    bytes: [u8; 4],
+    length: u8,
+}
+
+impl From<char> for EncodedChar {
+    fn from(character: char) -> Self {
+        let mut bytes = [0; 4];
+        let length = character.encode_utf8(&mut bytes).len() as u8;
+
+        EncodedChar { bytes, length }
+    }
+}
+
+impl EncodedChar {
+    pub fn as_str(&self) -> &str {
+        str::from_utf8(&self.bytes[..self.length as usize])
+            .expect("Encoded character is not valid UTF-8")
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum SourceChar {
+    Encoded(EncodedChar),
+    Shared(Arc<str>, usize),
+}
+
+impl From<char> for SourceChar {
+    fn from(character: char) -> Self {
+        SourceChar::Encoded(EncodedChar::from(character))
+    }
+}
+
+impl SourceChar {
+    pub fn next_from(input: &mut impl Input) -> Option<Self> {
+        let start = input.position().byte_offset;
+        let character = input.next()?;
+        let decoded = input.position().byte_offset - start == character.len_utf8();
+
+        Some(match input.shared_source() {
+            Some(source) if decoded => SourceChar::Shared(Arc::clone(source), start),
+            _ => SourceChar::from(character),
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            SourceChar::Encoded(character) => character.as_str(),
+            SourceChar::Shared(source, start) => {
+                let length = source[*start..]
+                    .chars()
+                    .next()
+                    .map(char::len_utf8)
+                    .expect("Shared character is out of its source");
+
+                &source[*start..*start + length]
+            }
+        }
+    }
+
+    pub fn source_slice(&self) -> SourceSlice<'_> {
+        match self {
+            SourceChar::Encoded(character) if character.as_str() == "\u{fffd}" => {
+                SourceSlice::Detached
+            }
+            SourceChar::Encoded(character) => SourceSlice::Unanchored(character.length as usize),
+            SourceChar::Shared(source, start) => {
+                SourceSlice::anchored(source, *start, *start + self.as_str().len())
+            }
+        }
+    }
+}
+
+impl PartialEq for SourceChar {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for SourceChar {}
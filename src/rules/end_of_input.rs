@@ -0,0 +1,92 @@
+use {
+    crate::{
+        grammar::{Grammar, GrammarNode},
+        input::{Input, SourceSlice},
+        Expected, ParseError, PegAstNode,
+    },
+    std::borrow::Cow,
+};
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct EndOfInput;
+
+impl PegAstNode for EndOfInput {
+    fn parse(input: &mut impl Input) -> Result<Self, ParseError> {
+        match input.peek() {
+            None => Ok(EndOfInput),
+            Some(_) => Err(ParseError::new(Self::expecting(), input.position())),
+        }
+    }
+
+    fn parsed_string(&self) -> Cow<'_, str> {
+        Cow::Borrowed("")
+    }
+
+    fn parsed_string_length(&self) -> usize {
+        0
+    }
+
+    fn source_slice(&self) -> SourceSlice<'_> {
+        SourceSlice::Empty
+    }
+
+    fn expecting() -> Vec<Expected> {
+        vec![Expected::EndOfInput]
+    }
+
+    fn grammar_node(_grammar: &mut Grammar) -> GrammarNode {
+        GrammarNode::EndOfInput
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::EndOfInput,
+        crate::{
+            input::StrInput,
+            literals, parse_complete, ranges,
+            rules::{Literal, OneOrMore, Range},
+            Expected, PegAstNode,
+        },
+    };
+
+    literals!(Plus: "+");
+    ranges!(Digit: ['0', '9']);
+
+    type Number = OneOrMore<Range<Digit>>;
+
+    #[derive(Clone, Debug, pegast_derive::PegAstNode, pegast_derive::FromStr)]
+    struct Sum {
+        left: Number,
+        plus: Literal<Plus>,
+        right: Number,
+    }
+
+    #[test]
+    fn matches_only_at_the_end() {
+        assert!(EndOfInput::parse(&mut StrInput::new("")).is_ok());
+        assert!(EndOfInput::parse(&mut StrInput::new("x")).is_err());
+    }
+
+    #[test]
+    fn parse_complete_rejects_trailing_input() {
+        let error = parse_complete::<Number>(&mut StrInput::new("12abc")).unwrap_err();
+
+        assert_eq!(error.position.byte_offset, 2);
+        assert_eq!(
+            error.expected,
+            [Expected::CharRange('0', '9'), Expected::EndOfInput]
+        );
+    }
+
+    #[test]
+    fn derived_from_str_requires_the_whole_string() {
+        assert_eq!("1+23".parse::<Sum>().unwrap().parsed_string(), "1+23");
+
+        let error = "1+2garbage".parse::<Sum>().unwrap_err();
+
+        assert_eq!(error.position.byte_offset, 3);
+        assert!(error.expected.contains(&Expected::EndOfInput));
+    }
+}
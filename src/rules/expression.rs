@@ -0,0 +1,471 @@
+use {
+    crate::{
+        grammar::{Grammar, GrammarNode},
+        input::{Input, SourceSlice},
+        Expected, ParseError, PegAstNode,
+    },
+    std::{any::Any, borrow::Cow, fmt::Debug},
+};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Associativity {
+    Left,
+    Right,
+}
+
+pub trait ExpressionGrammar: Debug + 'static {
+    type Atom: PegAstNode;
+    type Prefix: PegAstNode;
+    type Infix: PegAstNode;
+    type Postfix: PegAstNode;
+
+    fn prefix_precedence(_operator: &Self::Prefix) -> u8 {
+        0
+    }
+
+    fn infix_precedence(operator: &Self::Infix) -> (u8, Associativity);
+
+    fn postfix_precedence(_operator: &Self::Postfix) -> u8 {
+        0
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NoOperator {}
+
+impl PegAstNode for NoOperator {
+    fn parse(input: &mut impl Input) -> Result<Self, ParseError> {
+        Err(ParseError::new(Vec::new(), input.position()))
+    }
+
+    fn parsed_string(&self) -> Cow<'_, str> {
+        match *self {}
+    }
+
+    fn expecting() -> Vec<Expected> {
+        Vec::new()
+    }
+
+    fn grammar_node(_grammar: &mut Grammar) -> GrammarNode {
+        GrammarNode::Never
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Expression<G: ExpressionGrammar> {
+    Atom(G::Atom),
+    Prefix(G::Prefix, Box<Expression<G>>),
+    Infix(Box<Expression<G>>, G::Infix, Box<Expression<G>>),
+    Postfix(Box<Expression<G>>, G::Postfix),
+}
+
+impl<G: ExpressionGrammar> Expression<G> {
+    fn parse_with_binding_power(
+        input: &mut impl Input,
+        min_power: u16,
+    ) -> Result<Self, ParseError> {
+        let mut left = match try_parse::<G::Prefix>(input)? {
+            Some(operator) => {
+                let power = 2 * u16::from(G::prefix_precedence(&operator));
+                let operand = Self::parse_with_binding_power(input, power)
+                    .map_err(|error| input.state().fail(error))?;
+
+                Expression::Prefix(operator, Box::new(operand))
+            }
+            None => {
+                Expression::Atom(G::Atom::parse(input).map_err(|error| input.state().fail(error))?)
+            }
+        };
+
+        loop {
+            let checkpoint = input.state().checkpoint();
+
+            let postfix = {
+                let mut peek_input = input.peek_only();
+
+                try_parse::<G::Postfix>(&mut peek_input)?
+                    .map(|operator| (peek_input.position(), operator))
+            };
+
+            if let Some((new_position, operator)) = postfix {
+                if 2 * u16::from(G::postfix_precedence(&operator)) < min_power {
+                    input.state().rollback(checkpoint);
+                    break;
+                }
+
+                input.advance_to(new_position);
+                left = Expression::Postfix(Box::new(left), operator);
+                continue;
+            }
+
+            let infix = {
+                let mut peek_input = input.peek_only();
+
+                try_parse::<G::Infix>(&mut peek_input)?
+                    .map(|operator| (peek_input.position(), operator))
+            };
+            let (operator_end, operator) = match infix {
+                Some(infix) => infix,
+                None => break,
+            };
+
+            let (precedence, associativity) = G::infix_precedence(&operator);
+            let (left_power, right_power) = match associativity {
+                Associativity::Left => (2 * u16::from(precedence), 2 * u16::from(precedence) + 1),
+                Associativity::Right => (2 * u16::from(precedence) + 1, 2 * u16::from(precedence)),
+            };
+
+            if left_power < min_power {
+                input.state().rollback(checkpoint);
+                break;
+            }
+
+            let right = {
+                let mut peek_input = input.peek_only();
+
+                peek_input.advance_to(operator_end);
+
+                Self::parse_with_binding_power(&mut peek_input, right_power)
+                    .map(|right| (peek_input.position(), right))
+            };
+
+            match right {
+                Ok((new_position, right)) => {
+                    input.advance_to(new_position);
+                    left = Expression::Infix(Box::new(left), operator, Box::new(right));
+                }
+                Err(error) if error.cut => return Err(input.state().fail(error)),
+                Err(error) => {
+                    input.state().rollback(checkpoint);
+                    input.state().record_failure(error);
+                    break;
+                }
+            }
+        }
+
+        Ok(left)
+    }
+}
+
+impl<G: ExpressionGrammar> PegAstNode for Expression<G> {
+    fn parse(input: &mut impl Input) -> Result<Self, ParseError> {
+        let (new_position, expression) = {
+            let mut peek_input = input.peek_only();
+            let expression = Self::parse_with_binding_power(&mut peek_input, 0)?;
+
+            (peek_input.position(), expression)
+        };
+
+        input.advance_to(new_position);
+
+        Ok(expression)
+    }
+
+    fn parsed_string(&self) -> Cow<'_, str> {
+        if let Some(text) = self.source_slice().as_str() {
+            return Cow::Borrowed(text);
+        }
+
+        match self {
+            Expression::Atom(atom) => atom.parsed_string(),
+            Expression::Prefix(operator, operand) => {
+                let mut string = operator.parsed_string().into_owned();
+
+                string.push_str(&operand.parsed_string());
+
+                Cow::Owned(string)
+            }
+            Expression::Infix(left, operator, right) => {
+                let mut string = left.parsed_string().into_owned();
+
+                string.push_str(&operator.parsed_string());
+                string.push_str(&right.parsed_string());
+
+                Cow::Owned(string)
+            }
+            Expression::Postfix(operand, operator) => {
+                let mut string = operand.parsed_string().into_owned();
+
+                string.push_str(&operator.parsed_string());
+
+                Cow::Owned(string)
+            }
+        }
+    }
+
+    fn parsed_string_length(&self) -> usize {
+        match self {
+            Expression::Atom(atom) => atom.parsed_string_length(),
+            Expression::Prefix(operator, operand) => {
+                operator.parsed_string_length() + operand.parsed_string_length()
+            }
+            Expression::Infix(left, operator, right) => {
+                left.parsed_string_length()
+                    + operator.parsed_string_length()
+                    + right.parsed_string_length()
+            }
+            Expression::Postfix(operand, operator) => {
+                operand.parsed_string_length() + operator.parsed_string_length()
+            }
+        }
+    }
+
+    fn source_slice(&self) -> SourceSlice<'_> {
+        match self {
+            Expression::Atom(atom) => atom.source_slice(),
+            Expression::Prefix(operator, operand) => {
+                operator.source_slice().then(operand.source_slice())
+            }
+            Expression::Infix(left, operator, right) => left
+                .source_slice()
+                .then(operator.source_slice())
+                .then(right.source_slice()),
+            Expression::Postfix(operand, operator) => {
+                operand.source_slice().then(operator.source_slice())
+            }
+        }
+    }
+
+    fn expecting() -> Vec<Expected> {
+        let mut expecting = G::Prefix::expecting();
+
+        expecting.extend(G::Atom::expecting());
+        expecting
+    }
+
+    fn grammar_node(grammar: &mut Grammar) -> GrammarNode {
+        grammar.rule::<Self>(|grammar| {
+            let operand = GrammarNode::sequence(vec![
+                Vec::<G::Prefix>::grammar_node(grammar),
+                G::Atom::grammar_node(grammar),
+            ]);
+            let postfix = G::Postfix::grammar_node(grammar);
+            let infix = G::Infix::grammar_node(grammar);
+
+            GrammarNode::sequence(vec![
+                operand.clone(),
+                GrammarNode::zero_or_more(GrammarNode::choice(vec![
+                    postfix,
+                    GrammarNode::sequence(vec![infix, operand]),
+                ])),
+            ])
+        })
+    }
+
+    fn visit_nodes<'node>(&'node self, visitor: &mut dyn FnMut(&'node dyn Any))
+    where
+        Self: 'static,
+    {
+        visitor(self);
+
+        match self {
+            Expression::Atom(atom) => atom.visit_nodes(visitor),
+            Expression::Prefix(operator, operand) => {
+                operator.visit_nodes(visitor);
+                operand.visit_nodes(visitor);
+            }
+            Expression::Infix(left, operator, right) => {
+                left.visit_nodes(visitor);
+                operator.visit_nodes(visitor);
+                right.visit_nodes(visitor);
+            }
+            Expression::Postfix(operand, operator) => {
+                operand.visit_nodes(visitor);
+                operator.visit_nodes(visitor);
+            }
+        }
+    }
+}
+
+fn try_parse<T: PegAstNode>(input: &mut impl Input) -> Result<Option<T>, ParseError> {
+    let checkpoint = input.state().checkpoint();
+    let start = input.position();
+
+    input.state().mark_boundary(start);
+
+    match T::parse(input) {
+        Ok(node) => {
+            let end = input.position();
+
+            input.state().mark_boundary(end);
+
+            Ok(Some(node))
+        }
+        Err(error) if error.cut => Err(input.state().fail(error)),
+        Err(error) => {
+            input.state().rollback(checkpoint);
+            input.state().record_failure(error);
+
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{Associativity, Expression, ExpressionGrammar, NoOperator},
+        crate::{
+            grammar::{Grammar, Notation},
+            input::StrInput,
+            literals, parse_complete, ranges,
+            rules::{Any, Literal, OneOrMore, Range},
+            PegAstNode,
+        },
+    };
+
+    literals!(
+        Plus: "+",
+        Minus: "-",
+        Star: "*",
+        Caret: "^",
+        Bang: "!",
+        Arrow: "->",
+        Open: "(",
+        Close: ")",
+    );
+    ranges!(Digit: ['0', '9']);
+
+    type Number = OneOrMore<Range<Digit>>;
+
+    #[derive(Clone, Debug)]
+    struct Arithmetic;
+
+    #[derive(Clone, Debug, pegast_derive::PegAstNode)]
+    enum Atom {
+        Number(Number),
+        Group(
+            #[pegast(cut)] Literal<Open>,
+            Box<Expression<Arithmetic>>,
+            Literal<Close>,
+        ),
+    }
+
+    #[derive(Clone, Debug, pegast_derive::PegAstNode)]
+    enum Operator {
+        Plus(Literal<Plus>),
+        Minus(Literal<Minus>),
+        Star(Literal<Star>),
+        Caret(Literal<Caret>),
+    }
+
+    impl ExpressionGrammar for Arithmetic {
+        type Atom = Atom;
+        type Prefix = Literal<Minus>;
+        type Infix = Operator;
+        type Postfix = Literal<Bang>;
+
+        fn prefix_precedence(_operator: &Self::Prefix) -> u8 {
+            4
+        }
+
+        fn infix_precedence(operator: &Self::Infix) -> (u8, Associativity) {
+            match operator {
+                Operator::Plus(_) | Operator::Minus(_) => (1, Associativity::Left),
+                Operator::Star(_) => (2, Associativity::Left),
+                Operator::Caret(_) => (3, Associativity::Right),
+            }
+        }
+
+        fn postfix_precedence(_operator: &Self::Postfix) -> u8 {
+            5
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    struct Sums;
+
+    impl ExpressionGrammar for Sums {
+        type Atom = Number;
+        type Prefix = NoOperator;
+        type Infix = Literal<Plus>;
+        type Postfix = NoOperator;
+
+        fn infix_precedence(_operator: &Self::Infix) -> (u8, Associativity) {
+            (1, Associativity::Left)
+        }
+    }
+
+    fn shape(expression: &Expression<Arithmetic>) -> String {
+        match expression {
+            Expression::Atom(atom) => atom.parsed_string().into_owned(),
+            Expression::Prefix(operator, operand) => {
+                format!("({}{})", operator.parsed_string(), shape(operand))
+            }
+            Expression::Infix(left, operator, right) => format!(
+                "({} {} {})",
+                shape(left),
+                operator.parsed_string(),
+                shape(right)
+            ),
+            Expression::Postfix(operand, operator) => {
+                format!("({}{})", shape(operand), operator.parsed_string())
+            }
+        }
+    }
+
+    fn parse_shape(source: &str) -> String {
+        let expression =
+            parse_complete::<Expression<Arithmetic>>(&mut StrInput::new(source)).unwrap();
+
+        assert_eq!(expression.parsed_string(), source);
+
+        shape(&expression)
+    }
+
+    #[test]
+    fn binds_tighter_operators_first() {
+        assert_eq!(parse_shape("1+2*3"), "(1 + (2 * 3))");
+        assert_eq!(parse_shape("1*2+3"), "((1 * 2) + 3)");
+        assert_eq!(parse_shape("(1+2)*3"), "((1+2) * 3)");
+    }
+
+    #[test]
+    fn follows_operator_associativity() {
+        assert_eq!(parse_shape("1-2-3"), "((1 - 2) - 3)");
+        assert_eq!(parse_shape("2^3^2"), "(2 ^ (3 ^ 2))");
+    }
+
+    #[test]
+    fn parses_prefix_and_postfix_operators() {
+        assert_eq!(parse_shape("-1!+2"), "((-(1!)) + 2)");
+        assert_eq!(parse_shape("--1"), "(-(-1))");
+        assert_eq!(parse_shape("2*-3!"), "(2 * (-(3!)))");
+    }
+
+    #[test]
+    fn backtracks_before_an_infix_operator_without_a_right_operand() {
+        let mut input = StrInput::new("1->2");
+        let (expression, _, target) =
+            parse_complete::<(Expression<Arithmetic>, Literal<Arrow>, Number)>(&mut input).unwrap();
+
+        assert_eq!(shape(&expression), "1");
+        assert_eq!(target.parsed_string(), "2");
+    }
+
+    #[test]
+    fn does_not_backtrack_over_a_cut_in_the_right_operand() {
+        let mut input = StrInput::new("1+");
+        let (expression, rest) =
+            parse_complete::<(Expression<Arithmetic>, Vec<Any>)>(&mut input).unwrap();
+
+        assert_eq!(shape(&expression), "1");
+        assert_eq!(rest.parsed_string(), "+");
+
+        let mut input = StrInput::new("1+(2");
+        let error = parse_complete::<(Expression<Arithmetic>, Vec<Any>)>(&mut input).unwrap_err();
+
+        assert!(error.cut);
+        assert_eq!(error.position.byte_offset, 4);
+    }
+
+    #[test]
+    fn leaves_missing_operators_out_of_the_grammar() {
+        let grammar = Grammar::of::<Expression<Sums>>();
+
+        assert_eq!(
+            grammar.export(Notation::Peg),
+            "Expression_Sums <- [0-9]+ ( \"+\" [0-9]+ )*\n"
+        );
+    }
+}
@@ -0,0 +1,165 @@
+use {
+    super::{
+        literal::{ILiteral, LiteralValue},
+        Literal, UnicodeIdentContinue,
+    },
+    crate::{
+        grammar::{Grammar, GrammarNode},
+        input::{Input, SourceSlice},
+        Expected, ParseError, PegAstNode,
+    },
+    std::{borrow::Cow, marker::PhantomData},
+};
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Keyword<V: LiteralValue, B: PegAstNode = UnicodeIdentContinue>(PhantomData<(V, B)>);
+
+impl<V: LiteralValue, B: PegAstNode> Keyword<V, B> {
+    pub fn new() -> Self {
+        Keyword(PhantomData)
+    }
+}
+
+impl<V: LiteralValue, B: PegAstNode> PegAstNode for Keyword<V, B> {
+    fn parse(input: &mut impl Input) -> Result<Self, ParseError> {
+        parse_keyword::<Literal<V>, B>(input, Self::expecting()).map(|_| Keyword(PhantomData))
+    }
+
+    fn parsed_string(&self) -> Cow<'_, str> {
+        Cow::Borrowed(V::LITERAL)
+    }
+
+    fn parsed_string_length(&self) -> usize {
+        V::LITERAL.len()
+    }
+
+    fn source_slice(&self) -> SourceSlice<'_> {
+        SourceSlice::of_text(V::LITERAL)
+    }
+
+    fn expecting() -> Vec<Expected> {
+        vec![Expected::Keyword(V::LITERAL)]
+    }
+
+    fn grammar_node(grammar: &mut Grammar) -> GrammarNode {
+        GrammarNode::sequence(vec![
+            Literal::<V>::grammar_node(grammar),
+            GrammarNode::not_followed_by(B::grammar_node(grammar)),
+        ])
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IKeyword<V: LiteralValue, B: PegAstNode = UnicodeIdentContinue>(
+    ILiteral<V>,
+    PhantomData<B>,
+);
+
+impl<V: LiteralValue, B: PegAstNode> PegAstNode for IKeyword<V, B> {
+    fn parse(input: &mut impl Input) -> Result<Self, ParseError> {
+        parse_keyword::<ILiteral<V>, B>(input, Self::expecting())
+            .map(|literal| IKeyword(literal, PhantomData))
+    }
+
+    fn parsed_string(&self) -> Cow<'_, str> {
+        self.0.parsed_string()
+    }
+
+    fn parsed_string_length(&self) -> usize {
+        self.0.parsed_string_length()
+    }
+
+    fn source_slice(&self) -> SourceSlice<'_> {
+        self.0.source_slice()
+    }
+
+    fn expecting() -> Vec<Expected> {
+        vec![Expected::Keyword(V::LITERAL)]
+    }
+
+    fn grammar_node(grammar: &mut Grammar) -> GrammarNode {
+        GrammarNode::sequence(vec![
+            ILiteral::<V>::grammar_node(grammar),
+            GrammarNode::not_followed_by(B::grammar_node(grammar)),
+        ])
+    }
+}
+
+fn parse_keyword<L, B>(input: &mut impl Input, expecting: Vec<Expected>) -> Result<L, ParseError>
+where
+    L: PegAstNode,
+    B: PegAstNode,
+{
+    let start = input.position();
+    let (new_position, literal) = {
+        let mut peek_input = input.peek_only();
+
+        let literal = match L::parse(&mut peek_input) {
+            Ok(literal) => literal,
+            Err(_) => return Err(ParseError::new(expecting, start)),
+        };
+
+        let furthest_failure = peek_input.state().take_furthest_failure();
+        let checkpoint = peek_input.state().checkpoint();
+        let followed_by_word_character = {
+            let mut boundary_input = peek_input.peek_only();
+
+            B::parse(&mut boundary_input).is_ok()
+        };
+
+        peek_input.state().set_furthest_failure(furthest_failure);
+        peek_input.state().rollback(checkpoint);
+
+        if followed_by_word_character {
+            return Err(ParseError::new(expecting, start));
+        }
+
+        (peek_input.position(), literal)
+    };
+
+    input.advance_to(new_position);
+
+    Ok(literal)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{IKeyword, Keyword},
+        crate::{
+            input::{Input, StrInput},
+            literals, parse_complete, Expected, PegAstNode,
+        },
+    };
+
+    literals!(If: "if");
+
+    #[test]
+    fn matches_whole_words() {
+        let mut input = StrInput::new("if x");
+
+        assert!(Keyword::<If>::parse(&mut input).is_ok());
+        assert_eq!(input.position().byte_offset, 2);
+        assert!(parse_complete::<Keyword<If>>(&mut StrInput::new("if")).is_ok());
+    }
+
+    #[test]
+    fn rejects_keywords_followed_by_word_characters() {
+        let mut input = StrInput::new("iffy");
+        let error = Keyword::<If>::parse(&mut input).unwrap_err();
+
+        assert_eq!(error.position.byte_offset, 0);
+        assert_eq!(error.expected, [Expected::Keyword("if")]);
+        assert_eq!(input.position().byte_offset, 0);
+        assert!(Keyword::<If>::parse(&mut StrInput::new("ifé")).is_err());
+        assert!(Keyword::<If>::parse(&mut StrInput::new("if_")).is_err());
+    }
+
+    #[test]
+    fn case_insensitive_keywords_keep_the_source_text() {
+        let keyword = IKeyword::<If>::parse(&mut StrInput::new("IF(")).unwrap();
+
+        assert_eq!(keyword.parsed_string(), "IF");
+        assert!(IKeyword::<If>::parse(&mut StrInput::new("Iffy")).is_err());
+    }
+}
@@ -1,5 +1,9 @@
 use {
-    crate::{input::Input, ParseError, PegAstNode},
+    crate::{
+        grammar::{Grammar, GrammarNode},
+        input::{Input, SourceSlice},
+        Expected, ParseError, PegAstNode,
+    },
     std::{borrow::Cow, marker::PhantomData},
 };
 
@@ -7,6 +11,11 @@ use {
 pub struct Literal<V: LiteralValue>(PhantomData<V>);
 
 impl<V: LiteralValue> Literal<V> {
+    const CASE_SENSITIVE: () = assert!(
+        !V::IGNORE_CASE,
+        "ignore_case literal values must be parsed with ILiteral"
+    );
+
     pub fn new() -> Self {
         Literal(PhantomData)
     }
@@ -14,13 +23,17 @@ impl<V: LiteralValue> Literal<V> {
 
 impl<V: LiteralValue> PegAstNode for Literal<V> {
     fn parse(input: &mut impl Input) -> Result<Self, ParseError> {
+        let () = Self::CASE_SENSITIVE;
+        let start = input.position();
+
         if input.consume(V::LITERAL) {
+            let end = input.position();
+
+            input.state().mark_token(start, end);
+
             Ok(Literal(PhantomData))
         } else {
-            Err(ParseError {
-                expected: Self::expecting(),
-                position: input.position(),
-            })
+            Err(ParseError::new(Self::expecting(), input.position()))
         }
     }
 
@@ -32,17 +45,82 @@ impl<V: LiteralValue> PegAstNode for Literal<V> {
         V::LITERAL.len()
     }
 
-    fn expecting() -> Vec<String> {
-        vec![V::LITERAL.to_owned()]
+    fn source_slice(&self) -> SourceSlice<'_> {
+        SourceSlice::of_text(V::LITERAL)
+    }
+
+    fn expecting() -> Vec<Expected> {
+        vec![Expected::Literal(V::LITERAL)]
+    }
+
+    fn grammar_node(_grammar: &mut Grammar) -> GrammarNode {
+        GrammarNode::Literal(V::LITERAL)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ILiteral<V: LiteralValue>(String, PhantomData<V>);
+
+impl<V: LiteralValue> PegAstNode for ILiteral<V> {
+    fn parse(input: &mut impl Input) -> Result<Self, ParseError> {
+        let start = input.position();
+
+        if input.check_ignore_case(V::LITERAL) {
+            let written = V::LITERAL.chars().filter_map(|_| input.next()).collect();
+            let end = input.position();
+
+            input.state().mark_token(start, end);
+
+            Ok(ILiteral(written, PhantomData))
+        } else {
+            Err(ParseError::new(Self::expecting(), input.position()))
+        }
+    }
+
+    fn parsed_string(&self) -> Cow<'_, str> {
+        Cow::Borrowed(&self.0)
+    }
+
+    fn parsed_string_length(&self) -> usize {
+        self.0.len()
+    }
+
+    fn source_slice(&self) -> SourceSlice<'_> {
+        SourceSlice::of_text(&self.0)
+    }
+
+    fn expecting() -> Vec<Expected> {
+        vec![Expected::LiteralIgnoreCase(V::LITERAL)]
+    }
+
+    fn grammar_node(_grammar: &mut Grammar) -> GrammarNode {
+        GrammarNode::LiteralIgnoreCase(V::LITERAL)
     }
 }
 
 pub trait LiteralValue {
     const LITERAL: &'static str;
+    const IGNORE_CASE: bool = false;
 }
 
 #[macro_export]
 macro_rules! literals {
+    ( ignore_case $( $rest:tt )* ) => {
+        literals!(@ignore_case $( $rest )*);
+    };
+
+    ( @ignore_case $( $visibility:vis $name:ident : $literal:expr ),* $(,)? ) => {
+        $(
+            #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+            $visibility enum $name {}
+
+            impl pegast::rules::literal::LiteralValue for $name {
+                const LITERAL: &'static str = $literal;
+                const IGNORE_CASE: bool = true;
+            }
+        )*
+    };
+
     ( $name:ident : $literal:expr ) => {
         #[derive(Clone, Copy, Debug, Eq, PartialEq)]
         enum $name {}
@@ -75,3 +153,44 @@ macro_rules! literals {
 
     ( $(,)* ) => {};
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{ILiteral, Literal},
+        crate::{
+            input::StrInput,
+            parse_complete,
+            rules::{IKeyword, Keyword},
+            PegAstNode,
+        },
+        std::mem::size_of,
+    };
+
+    literals!(Arrow: "->");
+    literals!(ignore_case Select: "select");
+
+    #[test]
+    fn literal_is_zero_sized() {
+        assert_eq!(size_of::<Literal<Arrow>>(), 0);
+        assert_eq!(size_of::<Keyword<Arrow>>(), 0);
+    }
+
+    #[test]
+    fn ignore_case_literal_keeps_written_text() {
+        let mut input = StrInput::new("SeLeCt");
+        let literal = parse_complete::<ILiteral<Select>>(&mut input).unwrap();
+
+        assert_eq!(literal.parsed_string(), "SeLeCt");
+        assert_eq!(literal.parsed_string_length(), 6);
+    }
+
+    #[test]
+    fn ignore_case_keyword_requires_boundary() {
+        let mut input = StrInput::new("SELECT");
+        let keyword = parse_complete::<IKeyword<Select>>(&mut input).unwrap();
+
+        assert_eq!(keyword.parsed_string(), "SELECT");
+        assert!(parse_complete::<IKeyword<Select>>(&mut StrInput::new("SELECTED")).is_err());
+    }
+}
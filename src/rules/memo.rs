@@ -0,0 +1,346 @@
+use {
+    crate::{
+        grammar::{Grammar, GrammarNode},
+        input::{Input, MemoLookup, MemoTable, MemoizingInput, Position, SideEffects, SourceSlice},
+        Expected, ParseError, PegAstNode,
+    },
+    std::{
+        any::Any,
+        any::TypeId,
+        borrow::Cow,
+        cell::RefCell,
+        collections::HashSet,
+        ops::{Deref, DerefMut},
+    },
+};
+
+type Recorded<T> = (T, SideEffects);
+
+thread_local! {
+    static EXPECTING_IN_PROGRESS: RefCell<HashSet<TypeId>> = RefCell::new(HashSet::new());
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Memo<T>(pub T);
+
+impl<T> Memo<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Memo<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Memo<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> PegAstNode for Memo<T>
+where
+    T: PegAstNode + Clone + 'static,
+{
+    fn parse(input: &mut impl Input) -> Result<Self, ParseError> {
+        memoized(input, |input| T::parse(input).map(Memo))
+    }
+
+    fn parsed_string(&self) -> Cow<'_, str> {
+        self.0.parsed_string()
+    }
+
+    fn parsed_string_length(&self) -> usize {
+        self.0.parsed_string_length()
+    }
+
+    fn source_slice(&self) -> SourceSlice<'_> {
+        self.0.source_slice()
+    }
+
+    fn expecting() -> Vec<Expected> {
+        expecting_once::<T>(T::expecting)
+    }
+
+    fn grammar_node(grammar: &mut Grammar) -> GrammarNode {
+        T::grammar_node(grammar)
+    }
+
+    fn visit_nodes<'node>(&'node self, visitor: &mut dyn FnMut(&'node dyn Any))
+    where
+        Self: 'static,
+    {
+        visitor(self);
+        self.0.visit_nodes(visitor);
+    }
+}
+
+pub fn memoized<T, I>(
+    input: &mut I,
+    parse: impl Fn(&mut Box<dyn Input + '_>) -> Result<T, ParseError>,
+) -> Result<T, ParseError>
+where
+    T: PegAstNode + Clone + 'static,
+    I: Input,
+{
+    let start = input.position();
+
+    if input.memo_table().is_none() {
+        let result = {
+            let mut memoizing_input = MemoizingInput::new(input.peek_only());
+
+            memoized(&mut memoizing_input, parse).map(|node| (node, memoizing_input.position()))
+        };
+
+        return advance_with(input, result);
+    }
+
+    match memo_table(input).lookup::<Recorded<T>>(start) {
+        MemoLookup::Found(result) => return replay_and_advance_with(input, result),
+        MemoLookup::LeftRecursion => return Err(ParseError::new(T::expecting(), start)),
+        MemoLookup::Missing => {}
+    }
+
+    memo_table(input).start::<Recorded<T>>(start);
+
+    let mut result = record_ahead(input, &parse);
+
+    if memo_table(input).is_left_recursive::<Recorded<T>>(start) {
+        loop {
+            memo_table(input).grow::<Recorded<T>>(start, result.clone());
+
+            let grown_result = record_ahead(input, &parse);
+            let has_grown = match (&grown_result, &result) {
+                (Ok((_, grown_end)), Ok((_, end))) => grown_end > end,
+                (Ok(_), Err(_)) => true,
+                (Err(error), _) if error.cut => {
+                    result = grown_result;
+                    break;
+                }
+                (Err(_), _) => false,
+            };
+
+            if has_grown {
+                result = grown_result;
+            } else {
+                break;
+            }
+        }
+    }
+
+    memo_table(input).finish::<Recorded<T>>(start, result.clone());
+
+    replay_and_advance_with(input, result)
+}
+
+pub fn expecting_once<T>(expecting: impl FnOnce() -> Vec<Expected>) -> Vec<Expected>
+where
+    T: 'static,
+{
+    let is_first_entry = EXPECTING_IN_PROGRESS
+        .with(|in_progress| in_progress.borrow_mut().insert(TypeId::of::<T>()));
+
+    if !is_first_entry {
+        return Vec::new();
+    }
+
+    let expected = expecting();
+
+    EXPECTING_IN_PROGRESS.with(|in_progress| in_progress.borrow_mut().remove(&TypeId::of::<T>()));
+
+    expected
+}
+
+fn parse_ahead<T, I>(
+    input: &mut I,
+    parse: &impl Fn(&mut Box<dyn Input + '_>) -> Result<T, ParseError>,
+) -> Result<(T, Position), ParseError>
+where
+    I: Input,
+{
+    let mut peek_input = input.peek_only();
+    let node = parse(&mut peek_input)?;
+
+    Ok((node, peek_input.position()))
+}
+
+fn record_ahead<T, I>(
+    input: &mut I,
+    parse: &impl Fn(&mut Box<dyn Input + '_>) -> Result<T, ParseError>,
+) -> Result<(Recorded<T>, Position), ParseError>
+where
+    I: Input,
+{
+    let checkpoint = input.state().checkpoint();
+    let furthest_failure = input.state().take_furthest_failure();
+    let result = parse_ahead(input, parse);
+    let inner_failure = input.state().take_furthest_failure();
+
+    input.state().set_furthest_failure(furthest_failure);
+
+    match result {
+        Ok((node, end)) => {
+            let side_effects = input.state().side_effects_since(checkpoint, inner_failure);
+
+            input.state().rollback(checkpoint);
+
+            Ok(((node, side_effects), end))
+        }
+        Err(error) => {
+            input.state().rollback(checkpoint);
+
+            Err(match inner_failure {
+                Some(inner_failure) => inner_failure.merge(error.clone()).cut_if(error.cut),
+                None => error,
+            })
+        }
+    }
+}
+
+fn replay_and_advance_with<T>(
+    input: &mut impl Input,
+    result: Result<(Recorded<T>, Position), ParseError>,
+) -> Result<T, ParseError> {
+    let ((node, side_effects), end) =
+        result.inspect_err(|error| input.state().record_failure(error.clone()))?;
+
+    input.state().replay(&side_effects);
+    input.advance_to(end);
+
+    Ok(node)
+}
+
+fn advance_with<T>(
+    input: &mut impl Input,
+    result: Result<(T, Position), ParseError>,
+) -> Result<T, ParseError> {
+    let (node, end) = result?;
+
+    input.advance_to(end);
+
+    Ok(node)
+}
+
+fn memo_table(input: &mut impl Input) -> &mut MemoTable {
+    input
+        .memo_table()
+        .expect("Memoized parse requires a memoizing input")
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::Memo,
+        crate::{
+            input::{MemoizingInput, StrInput},
+            literals, parse_complete, ranges,
+            rules::{Literal, Not, OneOrMore, Range},
+            Expected, PegAstNode,
+        },
+    };
+
+    literals!(Plus: "+", Minus: "-", Semi: ";", Dot: ".");
+    ranges!(Digit: ['0', '9']);
+
+    type Number = OneOrMore<Range<Digit>>;
+
+    #[derive(Clone, Debug, pegast_derive::PegAstNode)]
+    #[pegast(memoize)]
+    enum Sum {
+        Add(Box<Sum>, Literal<Plus>, Number),
+        Number(Number),
+    }
+
+    #[derive(Clone, Debug, pegast_derive::PegAstNode)]
+    #[pegast(memoize)]
+    enum Difference {
+        Subtract(Box<Subtraction>),
+        Number(Number),
+    }
+
+    #[derive(Clone, Debug, pegast_derive::PegAstNode)]
+    struct Subtraction(Difference, Literal<Minus>, Number);
+
+    #[derive(Clone, Debug, pegast_derive::FromStr, pegast_derive::PegAstNode)]
+    struct Statement(Sum, Literal<Semi>);
+
+    #[derive(Clone, Debug, pegast_derive::PegAstNode)]
+    #[pegast(memoize)]
+    struct Item(Number, Option<Literal<Dot>>);
+
+    #[derive(Clone, Debug, pegast_derive::PegAstNode)]
+    struct Guarded(Not<(Item, Literal<Semi>)>, Item, Literal<Plus>);
+
+    fn left_operand(sum: &Sum) -> &Sum {
+        match sum {
+            Sum::Add(left, _, _) => left,
+            Sum::Number(_) => panic!("expected an addition"),
+        }
+    }
+
+    #[test]
+    fn grows_direct_left_recursion() {
+        let mut input = MemoizingInput::new(StrInput::new("1+2+3"));
+        let sum = parse_complete::<Sum>(&mut input).unwrap();
+
+        assert_eq!(sum.parsed_string(), "1+2+3");
+        assert_eq!(left_operand(&sum).parsed_string(), "1+2");
+        assert_eq!(left_operand(left_operand(&sum)).parsed_string(), "1");
+    }
+
+    #[test]
+    fn grows_indirect_left_recursion() {
+        let mut input = MemoizingInput::new(StrInput::new("3-2-1"));
+        let difference = parse_complete::<Difference>(&mut input).unwrap();
+
+        assert_eq!(difference.parsed_string(), "3-2-1");
+
+        match difference {
+            Difference::Subtract(subtraction) => {
+                assert_eq!(subtraction.0.parsed_string(), "3-2");
+                assert_eq!(subtraction.2.parsed_string(), "1");
+            }
+            Difference::Number(_) => panic!("expected a subtraction"),
+        }
+    }
+
+    #[test]
+    fn grows_left_recursion_without_memoizing_input() {
+        let mut input = StrInput::new("1+2+3");
+        let sum = parse_complete::<Sum>(&mut input).unwrap();
+
+        assert_eq!(left_operand(&sum).parsed_string(), "1+2");
+    }
+
+    #[test]
+    fn grows_left_recursion_nested_in_plain_rule() {
+        let statement = "1+2;".parse::<Statement>().unwrap();
+
+        assert_eq!(statement.parsed_string(), "1+2;");
+        assert_eq!(left_operand(&statement.0).parsed_string(), "1");
+    }
+
+    #[test]
+    fn memo_wraps_memoized_rule() {
+        let mut input = MemoizingInput::new(StrInput::new("1+2"));
+        let sum = parse_complete::<Memo<Sum>>(&mut input).unwrap();
+
+        assert_eq!(sum.parsed_string(), "1+2");
+        assert_eq!(left_operand(&sum).parsed_string(), "1");
+    }
+
+    #[test]
+    fn memo_hit_replays_furthest_failure() {
+        let mut input = MemoizingInput::new(StrInput::new("1*"));
+        let error = parse_complete::<Guarded>(&mut input).unwrap_err();
+
+        assert_eq!(error.position.byte_offset, 1);
+        assert!(error.expected.contains(&Expected::Literal(".")));
+        assert!(error.expected.contains(&Expected::Literal("+")));
+    }
+}
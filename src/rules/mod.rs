@@ -1,22 +1,47 @@
 mod all_of;
 mod any;
+pub mod bytes;
+pub mod char_class;
+mod cut;
 mod delimitted;
+mod encoded_char;
+mod end_of_input;
+pub mod expression;
+mod keyword;
 pub mod literal;
+pub mod memo;
 mod not;
 mod one_or_more;
 mod optional;
 mod peek;
 pub mod range;
+mod recover;
+mod repeat;
 pub mod sets;
+pub mod skipping;
+mod spanned;
+pub mod unicode;
 mod zero_or_more;
 
 pub use self::{
     any::Any,
+    bytes::{AnyByte, ByteLiteral, ByteRange},
+    char_class::CharClass,
+    cut::Cut,
     delimitted::Delimitted,
-    literal::Literal,
+    end_of_input::EndOfInput,
+    expression::Expression,
+    keyword::{IKeyword, Keyword},
+    literal::{ILiteral, Literal},
+    memo::Memo,
     not::Not,
     one_or_more::OneOrMore,
     peek::Peek,
     range::Range,
+    recover::{ErrorNode, Recover},
+    repeat::Repeat,
     sets::{DelimittedSetOf, SetOf},
+    skipping::Skipping,
+    spanned::Spanned,
+    unicode::{Category, UnicodeClass, UnicodeIdentContinue, UnicodeIdentStart, UnicodeWhitespace},
 };
@@ -1,5 +1,9 @@
 use {
-    crate::{input::Input, ParseError, PegAstNode},
+    crate::{
+        grammar::{Grammar, GrammarNode},
+        input::{Input, SourceSlice},
+        Expected, ParseError, PegAstNode,
+    },
     std::{borrow::Cow, marker::PhantomData},
 };
 
@@ -8,17 +12,19 @@ pub struct Not<R: PegAstNode>(PhantomData<R>);
 
 impl<R: PegAstNode> PegAstNode for Not<R> {
     fn parse(input: &mut impl Input) -> Result<Self, ParseError> {
+        let furthest_failure = input.state().take_furthest_failure();
+        let checkpoint = input.state().checkpoint();
         let parse_result = {
             let mut peek_only = input.peek_only();
 
             R::parse(&mut peek_only)
         };
 
+        input.state().set_furthest_failure(furthest_failure);
+        input.state().rollback(checkpoint);
+
         match parse_result {
-            Ok(_) => Err(ParseError {
-                expected: Self::expecting(),
-                position: input.position(),
-            }),
+            Ok(_) => Err(ParseError::new(Self::expecting(), input.position())),
             Err(_) => Ok(Not(PhantomData)),
         }
     }
@@ -31,24 +37,18 @@ impl<R: PegAstNode> PegAstNode for Not<R> {
         0
     }
 
-    fn expecting() -> Vec<String> {
-        let mut expecting = "not ".to_owned();
-        let not_expecting = R::expecting();
-
-        match not_expecting.len() {
-            0 => expecting.push_str("nothing"),
-            1 => expecting.push_str(&not_expecting[0]),
-            _ => {
-                expecting.push_str("one of:\n");
-
-                for element in not_expecting {
-                    expecting.push_str("    - ");
-                    expecting.push_str(&element);
-                    expecting.push('\n');
-                }
-            }
-        }
+    fn source_slice(&self) -> SourceSlice<'_> {
+        SourceSlice::Empty
+    }
+
+    fn expecting() -> Vec<Expected> {
+        R::expecting()
+            .into_iter()
+            .map(|expected| Expected::Not(Box::new(expected)))
+            .collect()
+    }
 
-        vec![expecting]
+    fn grammar_node(grammar: &mut Grammar) -> GrammarNode {
+        GrammarNode::not_followed_by(R::grammar_node(grammar))
     }
 }
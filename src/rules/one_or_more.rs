@@ -1,6 +1,14 @@
 use {
-    crate::{input::Input, ParseError, PegAstNode},
-    std::{borrow::Cow, iter},
+    super::skipping::{
+        parse_skipping_items, push_skipped_items, skipped_items_source_slice,
+        skipping_items_grammar_node,
+    },
+    crate::{
+        grammar::{Grammar, GrammarNode},
+        input::{Input, SourceSlice},
+        Expected, ParseError, PegAstNode,
+    },
+    std::{any::Any, borrow::Cow, iter},
 };
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -22,7 +30,7 @@ where
     fn parse(input: &mut impl Input) -> Result<Self, ParseError> {
         let (new_position, result) = {
             let mut peek_input = input.peek_only();
-            let head = T::parse(&mut peek_input)?;
+            let head = T::parse(&mut peek_input).map_err(|error| peek_input.state().fail(error))?;
             let tail = Vec::parse(&mut peek_input)?;
 
             (peek_input.position(), OneOrMore { head, tail })
@@ -37,13 +45,15 @@ where
         if self.tail.is_empty() {
             self.head.parsed_string()
         } else {
-            let mut string = self.head.parsed_string().into_owned();
+            self.source_slice().text_or_else(|| {
+                let mut string = self.head.parsed_string().into_owned();
 
-            for item in &self.tail {
-                string.push_str(&item.parsed_string());
-            }
+                for item in &self.tail {
+                    string.push_str(&item.parsed_string());
+                }
 
-            Cow::Owned(string)
+                string
+            })
         }
     }
 
@@ -51,7 +61,52 @@ where
         self.head.parsed_string_length() + self.tail.parsed_string_length()
     }
 
-    fn expecting() -> Vec<String> {
+    fn source_slice(&self) -> SourceSlice<'_> {
+        self.head.source_slice().then(self.tail.source_slice())
+    }
+
+    fn expecting() -> Vec<Expected> {
         T::expecting()
     }
+
+    fn grammar_node(grammar: &mut Grammar) -> GrammarNode {
+        GrammarNode::one_or_more(T::grammar_node(grammar))
+    }
+
+    fn parse_skipping<Trivia: PegAstNode>(
+        input: &mut impl Input,
+        trivia: &mut Vec<String>,
+    ) -> Result<Self, ParseError> {
+        let head = T::parse(input).map_err(|error| input.state().fail(error))?;
+        let mut tail = parse_skipping_items::<T, Trivia>(input, trivia, vec![head])?;
+        let head = tail.remove(0);
+
+        Ok(OneOrMore { head, tail })
+    }
+
+    fn push_skipped<'t>(&self, string: &mut String, trivia: &mut impl Iterator<Item = &'t String>) {
+        push_skipped_items(self.iter(), string, trivia)
+    }
+
+    fn skipped_source_slice<'a>(
+        &'a self,
+        trivia: &mut impl Iterator<Item = &'a String>,
+    ) -> SourceSlice<'a> {
+        skipped_items_source_slice(self.iter(), trivia)
+    }
+
+    fn skipping_grammar_node<Trivia: PegAstNode>(grammar: &mut Grammar) -> GrammarNode {
+        skipping_items_grammar_node::<T, Trivia>(grammar)
+    }
+
+    fn visit_nodes<'node>(&'node self, visitor: &mut dyn FnMut(&'node dyn Any))
+    where
+        Self: 'static,
+    {
+        visitor(self);
+
+        for element in self.iter() {
+            element.visit_nodes(visitor);
+        }
+    }
 }
@@ -1,11 +1,25 @@
 use {
-    crate::{input::Input, ParseError, PegAstNode},
-    std::borrow::Cow,
+    crate::{
+        grammar::{Grammar, GrammarNode},
+        input::{Input, SourceSlice},
+        Expected, ParseError, PegAstNode,
+    },
+    std::{any::Any, borrow::Cow},
 };
 
 impl<R: PegAstNode> PegAstNode for Option<R> {
     fn parse(input: &mut impl Input) -> Result<Self, ParseError> {
-        Ok(R::parse(input).ok())
+        let checkpoint = input.state().checkpoint();
+
+        match R::parse(input) {
+            Ok(node) => Ok(Some(node)),
+            Err(error) if error.cut => Err(error),
+            Err(error) => {
+                input.state().rollback(checkpoint);
+                input.state().record_failure(error);
+                Ok(None)
+            }
+        }
     }
 
     fn parsed_string(&self) -> Cow<'_, str> {
@@ -21,11 +35,68 @@ impl<R: PegAstNode> PegAstNode for Option<R> {
             .unwrap_or(0)
     }
 
-    fn expecting() -> Vec<String> {
-        let mut expecting = R::expecting();
+    fn source_slice(&self) -> SourceSlice<'_> {
+        match self {
+            Some(node) => node.source_slice(),
+            None => SourceSlice::Empty,
+        }
+    }
+
+    fn expecting() -> Vec<Expected> {
+        R::expecting()
+    }
+
+    fn grammar_node(grammar: &mut Grammar) -> GrammarNode {
+        GrammarNode::optional(R::grammar_node(grammar))
+    }
 
-        expecting.push("or nothing".to_owned());
+    fn parse_skipping<Trivia: PegAstNode>(
+        input: &mut impl Input,
+        trivia: &mut Vec<String>,
+    ) -> Result<Self, ParseError> {
+        let checkpoint = input.state().checkpoint();
+        let skipped = trivia.len();
+
+        match R::parse_skipping::<Trivia>(input, trivia) {
+            Ok(node) => Ok(Some(node)),
+            Err(error) if error.cut => Err(error),
+            Err(error) => {
+                trivia.truncate(skipped);
+                input.state().rollback(checkpoint);
+                input.state().record_failure(error);
+                Ok(None)
+            }
+        }
+    }
 
-        expecting
+    fn push_skipped<'t>(&self, string: &mut String, trivia: &mut impl Iterator<Item = &'t String>) {
+        if let Some(node) = self {
+            node.push_skipped(string, trivia);
+        }
+    }
+
+    fn skipped_source_slice<'a>(
+        &'a self,
+        trivia: &mut impl Iterator<Item = &'a String>,
+    ) -> SourceSlice<'a> {
+        match self {
+            Some(node) => node.skipped_source_slice(trivia),
+            None => SourceSlice::Empty,
+        }
+    }
+
+    fn skipping_grammar_node<Trivia: PegAstNode>(grammar: &mut Grammar) -> GrammarNode {
+        GrammarNode::optional(R::skipping_grammar_node::<Trivia>(grammar))
+    }
+
+    fn visit_nodes<'node>(&'node self, visitor: &mut dyn FnMut(&'node dyn Any))
+    where
+        Self: 'static,
+    {
+        visitor(self);
+
+        if let Some(node) = self {
+            node.visit_nodes(visitor);
+        }
     }
 }
@@ -1,5 +1,9 @@
 use {
-    crate::{input::Input, ParseError, PegAstNode},
+    crate::{
+        grammar::{Grammar, GrammarNode},
+        input::{Input, SourceSlice},
+        Expected, ParseError, PegAstNode,
+    },
     std::{borrow::Cow, marker::PhantomData},
 };
 
@@ -8,9 +12,20 @@ pub struct Peek<R: PegAstNode>(PhantomData<R>);
 
 impl<R: PegAstNode> PegAstNode for Peek<R> {
     fn parse(input: &mut impl Input) -> Result<Self, ParseError> {
-        let mut peek_only = input.peek_only();
+        let checkpoint = input.state().checkpoint();
+        let parse_result = {
+            let mut peek_only = input.peek_only();
 
-        R::parse(&mut peek_only)?;
+            R::parse(&mut peek_only)
+        };
+
+        input.state().rollback(checkpoint);
+
+        if let Err(mut error) = parse_result {
+            error.cut = false;
+
+            return Err(error);
+        }
 
         Ok(Peek(PhantomData))
     }
@@ -23,7 +38,15 @@ impl<R: PegAstNode> PegAstNode for Peek<R> {
         0
     }
 
-    fn expecting() -> Vec<String> {
+    fn source_slice(&self) -> SourceSlice<'_> {
+        SourceSlice::Empty
+    }
+
+    fn expecting() -> Vec<Expected> {
         R::expecting()
     }
+
+    fn grammar_node(grammar: &mut Grammar) -> GrammarNode {
+        GrammarNode::followed_by(R::grammar_node(grammar))
+    }
 }
@@ -1,5 +1,10 @@
 use {
-    crate::{input::Input, ParseError, PegAstNode},
+    super::encoded_char::SourceChar,
+    crate::{
+        grammar::{Grammar, GrammarNode},
+        input::{Input, SourceSlice},
+        Expected, ParseError, PegAstNode,
+    },
     std::{
         borrow::Cow,
         convert::TryFrom,
@@ -26,15 +31,15 @@ impl<V: RangeValue> Display for OutOfRangeError<V> {
 
 impl<V: RangeValue> Error for OutOfRangeError<V> {}
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-pub struct Range<V: RangeValue>(char, PhantomData<V>);
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Range<V: RangeValue>(SourceChar, PhantomData<V>);
 
 impl<V: RangeValue> TryFrom<char> for Range<V> {
     type Error = OutOfRangeError<V>;
 
     fn try_from(character: char) -> Result<Self, Self::Error> {
         if character >= V::START && character <= V::END {
-            Ok(Range(character, PhantomData))
+            Ok(Range(SourceChar::from(character), PhantomData))
         } else {
             Err(OutOfRangeError(character, PhantomData))
         }
@@ -43,33 +48,40 @@ impl<V: RangeValue> TryFrom<char> for Range<V> {
 
 impl<V: RangeValue> PegAstNode for Range<V> {
     fn parse(input: &mut impl Input) -> Result<Self, ParseError> {
+        let start = input.position();
+
         if let Some(input_char) = input.peek() {
-            if let Ok(result) = Self::try_from(input_char) {
-                let _ = input.next();
-                return Ok(result);
+            if Self::try_from(input_char).is_ok() {
+                let character = SourceChar::next_from(input).expect("Peeked character is missing");
+                let end = input.position();
+
+                input.state().mark_token(start, end);
+
+                return Ok(Range(character, PhantomData));
             }
         }
 
-        Err(ParseError {
-            expected: Self::expecting(),
-            position: input.position(),
-        })
+        Err(ParseError::new(Self::expecting(), input.position()))
     }
 
     fn parsed_string(&self) -> Cow<'_, str> {
-        Cow::Owned(self.0.to_string())
+        Cow::Borrowed(self.0.as_str())
     }
 
     fn parsed_string_length(&self) -> usize {
-        1
+        self.0.as_str().len()
     }
 
-    fn expecting() -> Vec<String> {
-        vec![format!(
-            "a character between '{}' and '{}'",
-            V::START,
-            V::END
-        )]
+    fn source_slice(&self) -> SourceSlice<'_> {
+        self.0.source_slice()
+    }
+
+    fn expecting() -> Vec<Expected> {
+        vec![Expected::CharRange(V::START, V::END)]
+    }
+
+    fn grammar_node(_grammar: &mut Grammar) -> GrammarNode {
+        GrammarNode::CharRange(V::START, V::END)
     }
 }
 
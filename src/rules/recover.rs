@@ -0,0 +1,287 @@
+use {
+    crate::{
+        grammar::{Grammar, GrammarNode},
+        input::{Input, SourceSlice, Span},
+        Expected, ParseError, PegAstNode,
+    },
+    std::{any::Any, borrow::Cow, marker::PhantomData},
+};
+
+#[derive(Clone, Debug)]
+pub struct ErrorNode {
+    text: String,
+    skipped_length: usize,
+    span: Span,
+    error: ParseError,
+}
+
+impl ErrorNode {
+    pub fn skipped(&self) -> &str {
+        &self.text[..self.skipped_length]
+    }
+
+    pub fn synchronization(&self) -> &str {
+        &self.text[self.skipped_length..]
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn error(&self) -> &ParseError {
+        &self.error
+    }
+}
+
+// `S` is parsed after the skipped text, so a synchronization token that also separates items,
+// as in `Delimitted<Recover<T, Peek<D>>, D>`, should be wrapped in `Peek` to leave it in place.
+#[derive(Clone, Debug)]
+pub enum Recover<T, S> {
+    Node(T),
+    Error(ErrorNode, PhantomData<S>),
+}
+
+impl<T, S> Recover<T, S> {
+    pub fn node(&self) -> Option<&T> {
+        match self {
+            Recover::Node(node) => Some(node),
+            Recover::Error(..) => None,
+        }
+    }
+
+    pub fn error(&self) -> Option<&ErrorNode> {
+        match self {
+            Recover::Node(_) => None,
+            Recover::Error(error, _) => Some(error),
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        matches!(self, Recover::Error(..))
+    }
+}
+
+impl<T, S> PegAstNode for Recover<T, S>
+where
+    T: PegAstNode,
+    S: PegAstNode,
+{
+    fn parse(input: &mut impl Input) -> Result<Self, ParseError> {
+        let furthest_failure = input.state().take_furthest_failure();
+        let checkpoint = input.state().checkpoint();
+        let start = input.position();
+        let parse_result = T::parse(input);
+        let inner_failure = input.state().take_furthest_failure();
+
+        input.state().set_furthest_failure(furthest_failure);
+
+        let error = match parse_result {
+            Ok(node) => {
+                if let Some(inner_failure) = inner_failure {
+                    input.state().record_failure(inner_failure);
+                }
+
+                return Ok(Recover::Node(node));
+            }
+            Err(error) => match inner_failure {
+                Some(inner_failure) => inner_failure.merge(error),
+                None => error,
+            },
+        };
+
+        if !input.state().is_recovering() {
+            return Err(input.state().fail(error));
+        }
+
+        input.state().rollback(checkpoint);
+
+        let furthest_failure = input.state().take_furthest_failure();
+        let mut skipped = String::new();
+        let synchronized = loop {
+            let found_synchronization = {
+                let mut peek_input = input.peek_only();
+
+                S::parse(&mut peek_input).is_ok()
+            };
+
+            input.state().rollback(checkpoint);
+
+            if found_synchronization {
+                break true;
+            }
+
+            match input.next() {
+                Some(character) => skipped.push(character),
+                None => break false,
+            }
+        };
+
+        let mut synchronization = String::new();
+
+        if synchronized {
+            match S::parse(input) {
+                Ok(node) => synchronization.push_str(&node.parsed_string()),
+                Err(_) => input.state().rollback(checkpoint),
+            }
+        }
+
+        if skipped.is_empty() && synchronization.is_empty() {
+            match input.next() {
+                Some(character) => skipped.push(character),
+                None => {
+                    input.state().set_furthest_failure(furthest_failure);
+
+                    return Err(input.state().fail(error));
+                }
+            }
+        }
+
+        input.state().set_furthest_failure(furthest_failure);
+
+        let skipped_length = skipped.len();
+        let mut text = skipped;
+
+        text.push_str(&synchronization);
+
+        let error = ParseError::new(error.expected, error.position);
+        let span = Span::new(start, input.position());
+
+        input.state().record_error(error.clone());
+
+        Ok(Recover::Error(
+            ErrorNode {
+                text,
+                skipped_length,
+                span,
+                error,
+            },
+            PhantomData,
+        ))
+    }
+
+    fn parsed_string(&self) -> Cow<'_, str> {
+        match self {
+            Recover::Node(node) => node.parsed_string(),
+            Recover::Error(error, _) => Cow::Borrowed(&error.text),
+        }
+    }
+
+    fn parsed_string_length(&self) -> usize {
+        match self {
+            Recover::Node(node) => node.parsed_string_length(),
+            Recover::Error(error, _) => error.text.len(),
+        }
+    }
+
+    fn source_slice(&self) -> SourceSlice<'_> {
+        match self {
+            Recover::Node(node) => node.source_slice(),
+            Recover::Error(error, _) => SourceSlice::of_text(&error.text),
+        }
+    }
+
+    fn expecting() -> Vec<Expected> {
+        T::expecting()
+    }
+
+    fn grammar_node(grammar: &mut Grammar) -> GrammarNode {
+        T::grammar_node(grammar)
+    }
+
+    fn visit_nodes<'node>(&'node self, visitor: &mut dyn FnMut(&'node dyn Any))
+    where
+        Self: 'static,
+    {
+        visitor(self);
+
+        if let Recover::Node(node) = self {
+            node.visit_nodes(visitor);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::Recover,
+        crate::{
+            input::StrInput,
+            literals, parse_recovering, ranges,
+            rules::{Delimitted, Literal, OneOrMore, Peek, Range},
+            PegAstNode,
+        },
+    };
+
+    literals!(Semi: ";");
+    ranges!(Lower: ['a', 'z']);
+
+    type Statement = (OneOrMore<Range<Lower>>, Literal<Semi>);
+    type Program = Vec<Recover<Statement, Literal<Semi>>>;
+
+    #[test]
+    fn consumes_synchronization_token_after_skipped_text() {
+        let mut input = StrInput::new("a;!!;b;c;");
+        let (program, errors) = parse_recovering::<Program>(&mut input);
+        let program = program.expect("partial tree");
+
+        assert_eq!(program.len(), 4);
+        assert!(program[1].is_error());
+        assert_eq!(program[1].error().unwrap().skipped(), "!!");
+        assert_eq!(program[1].error().unwrap().synchronization(), ";");
+        assert_eq!(program[1].error().unwrap().span().byte_range(), 2..5);
+        assert_eq!(program.iter().filter(|item| item.is_error()).count(), 1);
+        assert_eq!(errors.len(), 1);
+        assert_eq!((errors[0].position.line, errors[0].position.column), (1, 3));
+    }
+
+    #[test]
+    fn skips_to_end_of_input_without_synchronization() {
+        let mut input = StrInput::new("a;b");
+        let (program, errors) = parse_recovering::<Program>(&mut input);
+        let program = program.expect("partial tree");
+
+        assert_eq!(program.len(), 2);
+        assert_eq!(program[1].error().unwrap().skipped(), "b");
+        assert_eq!(program[1].error().unwrap().synchronization(), "");
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn skips_one_character_instead_of_producing_zero_width_errors() {
+        let mut input = StrInput::new("a;;b;");
+        let (program, errors) =
+            parse_recovering::<Vec<Recover<Statement, Peek<Literal<Semi>>>>>(&mut input);
+        let program = program.expect("partial tree");
+
+        assert_eq!(program.len(), 3);
+        assert!(!program[0].is_error());
+        assert_eq!(program[1].error().unwrap().skipped(), ";");
+        assert_eq!(program[1].error().unwrap().synchronization(), "");
+        assert_eq!(program[2].parsed_string(), "b;");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].position.byte_offset, 2);
+    }
+
+    #[test]
+    fn leaves_peeked_delimitters_for_the_enclosing_list() {
+        let mut input = StrInput::new("a;!!;b");
+        let (list, errors) = parse_recovering::<
+            Delimitted<Recover<OneOrMore<Range<Lower>>, Peek<Literal<Semi>>>, Literal<Semi>>,
+        >(&mut input);
+        let list = list.expect("partial tree");
+        let items = list.items().collect::<Vec<_>>();
+
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[1].error().unwrap().skipped(), "!!");
+        assert_eq!(items[2].parsed_string(), "b");
+        assert_eq!(list.parsed_string(), "a;!!;b");
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn fails_without_recovery() {
+        let mut input = StrInput::new("!!;");
+
+        assert!(crate::parse_complete::<Program>(&mut input).is_err());
+    }
+}
@@ -0,0 +1,230 @@
+use {
+    crate::{
+        grammar::{Grammar, GrammarNode},
+        input::{Input, SourceSlice},
+        Expected, ParseError, PegAstNode,
+    },
+    std::{any::Any, borrow::Cow, convert::TryInto, ops::Deref},
+};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Repeat<T, const MIN: usize, const MAX: usize = { usize::MAX }>(pub Vec<T>);
+
+impl<T, const MIN: usize, const MAX: usize> Repeat<T, MIN, MAX> {
+    const BOUNDS: () = assert!(MIN <= MAX, "Repeat requires MIN to be at most MAX");
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.0.iter()
+    }
+
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T, const MIN: usize, const MAX: usize> Deref for Repeat<T, MIN, MAX> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T, const MIN: usize, const MAX: usize> PegAstNode for Repeat<T, MIN, MAX>
+where
+    T: PegAstNode,
+{
+    fn parse(input: &mut impl Input) -> Result<Self, ParseError> {
+        let () = Self::BOUNDS;
+        let (new_position, nodes) = {
+            let mut peek_input = input.peek_only();
+            let mut nodes = Vec::new();
+
+            while nodes.len() < MAX {
+                let position = peek_input.position();
+                let checkpoint = peek_input.state().checkpoint();
+
+                match T::parse(&mut peek_input) {
+                    Ok(node) => {
+                        nodes.push(node);
+
+                        if peek_input.position() == position && nodes.len() >= MIN {
+                            break;
+                        }
+                    }
+                    Err(error) if error.cut => return Err(peek_input.state().fail(error)),
+                    Err(error) if nodes.len() < MIN => {
+                        let error = if error.position == peek_input.position() {
+                            ParseError::new(at_least(MIN, error.expected), error.position)
+                        } else {
+                            error
+                        };
+
+                        return Err(peek_input.state().fail(error));
+                    }
+                    Err(error) => {
+                        peek_input.state().rollback(checkpoint);
+                        peek_input.state().record_failure(error);
+                        break;
+                    }
+                }
+            }
+
+            (peek_input.position(), nodes)
+        };
+
+        input.advance_to(new_position);
+
+        Ok(Repeat(nodes))
+    }
+
+    fn parsed_string(&self) -> Cow<'_, str> {
+        self.0.parsed_string()
+    }
+
+    fn parsed_string_length(&self) -> usize {
+        self.0.parsed_string_length()
+    }
+
+    fn source_slice(&self) -> SourceSlice<'_> {
+        self.0.source_slice()
+    }
+
+    fn expecting() -> Vec<Expected> {
+        T::expecting()
+    }
+
+    fn grammar_node(grammar: &mut Grammar) -> GrammarNode {
+        let () = Self::BOUNDS;
+        let max = if MAX == usize::MAX { None } else { Some(MAX) };
+
+        GrammarNode::repeat(T::grammar_node(grammar), MIN, max)
+    }
+
+    fn visit_nodes<'node>(&'node self, visitor: &mut dyn FnMut(&'node dyn Any))
+    where
+        Self: 'static,
+    {
+        visitor(self);
+
+        for element in self.iter() {
+            element.visit_nodes(visitor);
+        }
+    }
+}
+
+impl<T, const N: usize> PegAstNode for [T; N]
+where
+    T: PegAstNode,
+{
+    fn parse(input: &mut impl Input) -> Result<Self, ParseError> {
+        let Repeat(nodes) = Repeat::<T, N, N>::parse(input)?;
+
+        nodes
+            .try_into()
+            .map_err(|_: Vec<T>| ParseError::new(at_least(N, T::expecting()), input.position()))
+    }
+
+    fn parsed_string(&self) -> Cow<'_, str> {
+        self.source_slice().text_or_else(|| {
+            let mut string = String::with_capacity(self.parsed_string_length());
+
+            for element in self {
+                string.push_str(&element.parsed_string());
+            }
+
+            string
+        })
+    }
+
+    fn parsed_string_length(&self) -> usize {
+        self.iter().map(PegAstNode::parsed_string_length).sum()
+    }
+
+    fn source_slice(&self) -> SourceSlice<'_> {
+        SourceSlice::join(self.iter().map(PegAstNode::source_slice))
+    }
+
+    fn expecting() -> Vec<Expected> {
+        T::expecting()
+    }
+
+    fn grammar_node(grammar: &mut Grammar) -> GrammarNode {
+        GrammarNode::repeat(T::grammar_node(grammar), N, Some(N))
+    }
+
+    fn visit_nodes<'node>(&'node self, visitor: &mut dyn FnMut(&'node dyn Any))
+    where
+        Self: 'static,
+    {
+        visitor(self);
+
+        for element in self {
+            element.visit_nodes(visitor);
+        }
+    }
+}
+
+fn at_least(count: usize, expected: Vec<Expected>) -> Vec<Expected> {
+    expected
+        .into_iter()
+        .map(|expected| Expected::AtLeast(count, Box::new(expected)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::Repeat,
+        crate::{input::StrInput, parse_complete, ranges, rules::Range, Expected, PegAstNode},
+    };
+
+    ranges!(Digit: ['0', '9']);
+
+    type Digits<const MIN: usize, const MAX: usize> = Repeat<Range<Digit>, MIN, MAX>;
+
+    #[test]
+    fn parses_an_exact_count() {
+        let digits = parse_complete::<Digits<3, 3>>(&mut StrInput::new("123")).unwrap();
+        let array = parse_complete::<[Range<Digit>; 3]>(&mut StrInput::new("456")).unwrap();
+
+        assert_eq!(digits.len(), 3);
+        assert_eq!(array.parsed_string(), "456");
+        assert!(parse_complete::<Digits<3, 3>>(&mut StrInput::new("1234")).is_err());
+    }
+
+    #[test]
+    fn parses_between_min_and_max() {
+        let minimum = parse_complete::<Digits<2, 4>>(&mut StrInput::new("12")).unwrap();
+        let maximum = parse_complete::<Digits<2, 4>>(&mut StrInput::new("1234")).unwrap();
+        let unbounded =
+            parse_complete::<Repeat<Range<Digit>, 2>>(&mut StrInput::new("123456")).unwrap();
+
+        assert_eq!(minimum.len(), 2);
+        assert_eq!(maximum.len(), 4);
+        assert_eq!(unbounded.parsed_string(), "123456");
+    }
+
+    #[test]
+    fn stops_at_max() {
+        let (digits, rest) =
+            parse_complete::<(Digits<0, 2>, Vec<Range<Digit>>)>(&mut StrInput::new("1234"))
+                .unwrap();
+
+        assert_eq!(digits.parsed_string(), "12");
+        assert_eq!(rest.parsed_string(), "34");
+    }
+
+    #[test]
+    fn reports_the_minimum_when_too_few_items_match() {
+        let error = parse_complete::<Digits<3, 5>>(&mut StrInput::new("12")).unwrap_err();
+        let at_least = Expected::AtLeast(3, Box::new(Expected::CharRange('0', '9')));
+
+        assert_eq!(error.position.byte_offset, 2);
+        assert!(error.expected.contains(&at_least));
+        assert_eq!(
+            at_least.to_string(),
+            "at least 3 of a character between '0' and '9'"
+        );
+    }
+}
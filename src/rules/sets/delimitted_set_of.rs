@@ -1,7 +1,11 @@
 use {
     super::SetEntries,
-    crate::{input::Input, ParseError, PegAstNode},
-    std::{borrow::Cow, collections::HashMap},
+    crate::{
+        grammar::{Grammar, GrammarNode},
+        input::{Input, SourceSlice},
+        Expected, ParseError, PegAstNode,
+    },
+    std::{any::Any, borrow::Cow, collections::HashMap},
 };
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -41,7 +45,7 @@ where
         let mut items = Vec::new();
         let mut delimitters = Vec::new();
         let mut entry_counts: HashMap<S::EntryId, usize> = S::all_entry_ids()
-            .into_iter()
+            .iter()
             .map(|&entry_id| (entry_id, 0))
             .collect();
 
@@ -49,8 +53,20 @@ where
             let mut peek_input = input.peek_only();
             let mut position = peek_input.position();
             let mut ending_with_delimitter = false;
+            let mut delimitter_checkpoint = peek_input.state().checkpoint();
+
+            loop {
+                let checkpoint = peek_input.state().checkpoint();
+                let item = match S::parse(&mut peek_input) {
+                    Ok(item) => item,
+                    Err(error) if error.cut => return Err(error),
+                    Err(error) => {
+                        peek_input.state().rollback(checkpoint);
+                        peek_input.state().record_failure(error);
+                        break;
+                    }
+                };
 
-            while let Ok(item) = S::parse(&mut peek_input) {
                 let entry_id = item.entry_id();
                 let count = entry_counts
                     .get_mut(&entry_id)
@@ -66,26 +82,32 @@ where
                 items.push(item);
                 position = peek_input.position();
                 ending_with_delimitter = false;
+                delimitter_checkpoint = peek_input.state().checkpoint();
 
-                if let Ok(delimitter) = D::parse(&mut peek_input) {
-                    delimitters.push(delimitter);
-                    ending_with_delimitter = true;
-                } else {
-                    break;
+                match D::parse(&mut peek_input) {
+                    Ok(delimitter) => {
+                        delimitters.push(delimitter);
+                        ending_with_delimitter = true;
+                    }
+                    Err(error) if error.cut => return Err(error),
+                    Err(error) => {
+                        peek_input.state().record_failure(error);
+                        break;
+                    }
                 }
             }
 
             for (entry_id, count) in entry_counts {
                 if count < S::min_repetitions(entry_id) {
-                    return Err(ParseError {
-                        expected: Self::expecting(),
-                        position: peek_input.position(),
-                    });
+                    let error = ParseError::new(Self::expecting(), peek_input.position());
+
+                    return Err(peek_input.state().fail(error));
                 }
             }
 
             if ending_with_delimitter {
                 delimitters.pop();
+                peek_input.state().rollback(delimitter_checkpoint);
             }
 
             position
@@ -97,18 +119,20 @@ where
     }
 
     fn parsed_string(&self) -> Cow<'_, str> {
-        let mut string = String::new();
+        self.source_slice().text_or_else(|| {
+            let mut string = String::new();
 
-        for (item, delimitter) in self.items.iter().zip(self.delimitters.iter()) {
-            string.push_str(&item.parsed_string());
-            string.push_str(&delimitter.parsed_string());
-        }
+            for (item, delimitter) in self.items.iter().zip(self.delimitters.iter()) {
+                string.push_str(&item.parsed_string());
+                string.push_str(&delimitter.parsed_string());
+            }
 
-        if let Some(last_item) = self.items.last() {
-            string.push_str(&last_item.parsed_string());
-        }
+            if let Some(last_item) = self.items.last() {
+                string.push_str(&last_item.parsed_string());
+            }
 
-        Cow::Owned(string)
+            string
+        })
     }
 
     fn parsed_string_length(&self) -> usize {
@@ -123,7 +147,47 @@ where
             .sum()
     }
 
-    fn expecting() -> Vec<String> {
+    fn source_slice(&self) -> SourceSlice<'_> {
+        let mut slice = SourceSlice::Empty;
+
+        for (item, delimitter) in self.items.iter().zip(self.delimitters.iter()) {
+            slice = slice
+                .then(item.source_slice())
+                .then(delimitter.source_slice());
+        }
+
+        match self.items.last() {
+            Some(last_item) => slice.then(last_item.source_slice()),
+            None => slice,
+        }
+    }
+
+    fn expecting() -> Vec<Expected> {
         S::expecting()
     }
+
+    fn grammar_node(grammar: &mut Grammar) -> GrammarNode {
+        let item = S::grammar_node(grammar);
+        let delimitter = D::grammar_node(grammar);
+
+        GrammarNode::optional(GrammarNode::sequence(vec![
+            item.clone(),
+            GrammarNode::zero_or_more(GrammarNode::sequence(vec![delimitter, item])),
+        ]))
+    }
+
+    fn visit_nodes<'node>(&'node self, visitor: &mut dyn FnMut(&'node dyn Any))
+    where
+        Self: 'static,
+    {
+        visitor(self);
+
+        for (index, item) in self.items.iter().enumerate() {
+            item.visit_nodes(visitor);
+
+            if let Some(delimitter) = self.delimitters.get(index) {
+                delimitter.visit_nodes(visitor);
+            }
+        }
+    }
 }
@@ -1,7 +1,12 @@
 use {
     super::SetEntries,
-    crate::{input::Input, ParseError, PegAstNode},
+    crate::{
+        grammar::{Grammar, GrammarNode},
+        input::{Input, SourceSlice},
+        Expected, ParseError, PegAstNode,
+    },
     std::{
+        any::Any,
         borrow::Cow,
         collections::HashMap,
         ops::{Deref, DerefMut},
@@ -38,14 +43,25 @@ where
     fn parse(input: &mut impl Input) -> Result<Self, ParseError> {
         let mut parsed_nodes = Vec::new();
         let mut entry_counts: HashMap<S::EntryId, usize> = S::all_entry_ids()
-            .into_iter()
+            .iter()
             .map(|&entry_id| (entry_id, 0))
             .collect();
 
         let new_position = {
             let mut peek_input = input.peek_only();
 
-            while let Ok(item) = S::parse(&mut peek_input) {
+            loop {
+                let checkpoint = peek_input.state().checkpoint();
+                let item = match S::parse(&mut peek_input) {
+                    Ok(item) => item,
+                    Err(error) if error.cut => return Err(error),
+                    Err(error) => {
+                        peek_input.state().rollback(checkpoint);
+                        peek_input.state().record_failure(error);
+                        break;
+                    }
+                };
+
                 let entry_id = item.entry_id();
                 let count = entry_counts
                     .get_mut(&entry_id)
@@ -63,10 +79,9 @@ where
 
             for (entry_id, count) in entry_counts {
                 if count < S::min_repetitions(entry_id) {
-                    return Err(ParseError {
-                        expected: Self::expecting(),
-                        position: peek_input.position(),
-                    });
+                    let error = ParseError::new(Self::expecting(), peek_input.position());
+
+                    return Err(peek_input.state().fail(error));
                 }
             }
 
@@ -86,7 +101,26 @@ where
         self.0.parsed_string_length()
     }
 
-    fn expecting() -> Vec<String> {
+    fn source_slice(&self) -> SourceSlice<'_> {
+        self.0.source_slice()
+    }
+
+    fn expecting() -> Vec<Expected> {
         S::expecting()
     }
+
+    fn grammar_node(grammar: &mut Grammar) -> GrammarNode {
+        GrammarNode::zero_or_more(S::grammar_node(grammar))
+    }
+
+    fn visit_nodes<'node>(&'node self, visitor: &mut dyn FnMut(&'node dyn Any))
+    where
+        Self: 'static,
+    {
+        visitor(self);
+
+        for entry in &self.0 {
+            entry.visit_nodes(visitor);
+        }
+    }
 }
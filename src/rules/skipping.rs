@@ -0,0 +1,368 @@
+use {
+    crate::{
+        grammar::{Grammar, GrammarNode},
+        input::{Input, SourceSlice},
+        Expected, ParseError, PegAstNode,
+    },
+    std::{
+        any::Any,
+        borrow::Cow,
+        ops::{Deref, DerefMut},
+    },
+};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Skipping<T, Trivia> {
+    pub trivia: Vec<Trivia>,
+    pub node: T,
+}
+
+impl<T, Trivia> Skipping<T, Trivia> {
+    pub fn into_inner(self) -> T {
+        self.node
+    }
+}
+
+impl<T, Trivia> Deref for Skipping<T, Trivia> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.node
+    }
+}
+
+impl<T, Trivia> DerefMut for Skipping<T, Trivia> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.node
+    }
+}
+
+impl<T, Trivia> PegAstNode for Skipping<T, Trivia>
+where
+    T: PegAstNode,
+    Trivia: PegAstNode,
+{
+    fn parse(input: &mut impl Input) -> Result<Self, ParseError> {
+        let (new_position, result) = {
+            let mut peek_input = input.peek_only();
+            let trivia = parse_trivia(&mut peek_input)?;
+            let node = T::parse(&mut peek_input).map_err(|error| peek_input.state().fail(error))?;
+
+            (peek_input.position(), Skipping { trivia, node })
+        };
+
+        input.advance_to(new_position);
+
+        Ok(result)
+    }
+
+    fn parsed_string(&self) -> Cow<'_, str> {
+        if self.trivia.is_empty() {
+            self.node.parsed_string()
+        } else {
+            self.source_slice().text_or_else(|| {
+                let mut string = self.trivia.parsed_string().into_owned();
+
+                string.push_str(&self.node.parsed_string());
+                string
+            })
+        }
+    }
+
+    fn parsed_string_length(&self) -> usize {
+        self.trivia.parsed_string_length() + self.node.parsed_string_length()
+    }
+
+    fn source_slice(&self) -> SourceSlice<'_> {
+        self.trivia.source_slice().then(self.node.source_slice())
+    }
+
+    fn expecting() -> Vec<Expected> {
+        let mut expecting = Trivia::expecting();
+
+        expecting.extend(T::expecting());
+        expecting
+    }
+
+    fn grammar_node(grammar: &mut Grammar) -> GrammarNode {
+        GrammarNode::sequence(vec![
+            Vec::<Trivia>::grammar_node(grammar),
+            T::grammar_node(grammar),
+        ])
+    }
+
+    fn visit_nodes<'node>(&'node self, visitor: &mut dyn FnMut(&'node dyn Any))
+    where
+        Self: 'static,
+    {
+        visitor(self);
+        self.node.visit_nodes(visitor);
+    }
+}
+
+pub(crate) fn parse_skipping_items<T, Trivia>(
+    input: &mut impl Input,
+    trivia: &mut Vec<String>,
+    mut nodes: Vec<T>,
+) -> Result<Vec<T>, ParseError>
+where
+    T: PegAstNode,
+    Trivia: PegAstNode,
+{
+    loop {
+        let position = input.position();
+        let checkpoint = input.state().checkpoint();
+        let attempt = {
+            let mut peek_input = input.peek_only();
+            let skipped = if nodes.is_empty() {
+                None
+            } else {
+                Some(skip_trivia::<Trivia>(&mut peek_input)?)
+            };
+
+            T::parse(&mut peek_input).map(|node| (peek_input.position(), skipped, node))
+        };
+
+        match attempt {
+            Ok((end, skipped, node)) => {
+                input.advance_to(end);
+                trivia.extend(skipped);
+                nodes.push(node);
+
+                if input.position() == position {
+                    return Ok(nodes);
+                }
+            }
+            Err(error) if error.cut => return Err(error),
+            Err(error) => {
+                input.state().rollback(checkpoint);
+                input.state().record_failure(error);
+                return Ok(nodes);
+            }
+        }
+    }
+}
+
+pub(crate) fn push_skipped_items<'a, 't, T: PegAstNode + 'a>(
+    items: impl Iterator<Item = &'a T>,
+    string: &mut String,
+    trivia: &mut impl Iterator<Item = &'t String>,
+) {
+    for (index, item) in items.enumerate() {
+        if index > 0 {
+            string.extend(trivia.next().map(String::as_str));
+        }
+
+        string.push_str(&item.parsed_string());
+    }
+}
+
+pub(crate) fn skipped_items_source_slice<'a, T: PegAstNode + 'a>(
+    items: impl Iterator<Item = &'a T>,
+    trivia: &mut impl Iterator<Item = &'a String>,
+) -> SourceSlice<'a> {
+    let mut slice = SourceSlice::Empty;
+
+    for (index, item) in items.enumerate() {
+        if index > 0 {
+            slice = slice.then(skipped_trivia_slice(trivia));
+        }
+
+        slice = slice.then(item.source_slice());
+    }
+
+    slice
+}
+
+pub(crate) fn skipped_trivia_slice<'a>(
+    trivia: &mut impl Iterator<Item = &'a String>,
+) -> SourceSlice<'a> {
+    trivia
+        .next()
+        .map_or(SourceSlice::Empty, |trivia| SourceSlice::of_text(trivia))
+}
+
+pub(crate) fn skipping_items_grammar_node<T, Trivia>(grammar: &mut Grammar) -> GrammarNode
+where
+    T: PegAstNode,
+    Trivia: PegAstNode,
+{
+    let node = T::grammar_node(grammar);
+
+    GrammarNode::sequence(vec![
+        node.clone(),
+        GrammarNode::zero_or_more(GrammarNode::sequence(vec![
+            Vec::<Trivia>::grammar_node(grammar),
+            node,
+        ])),
+    ])
+}
+
+pub fn skip_trivia<Trivia: PegAstNode>(input: &mut impl Input) -> Result<String, ParseError> {
+    parse_trivia::<Trivia>(input).map(|trivia| trivia.parsed_string().into_owned())
+}
+
+fn parse_trivia<Trivia: PegAstNode>(input: &mut impl Input) -> Result<Vec<Trivia>, ParseError> {
+    let trivia_start = input.position();
+
+    input.state().start_trivia(trivia_start);
+
+    let trivia = Vec::parse(input)?;
+    let trivia_end = input.position();
+
+    input.state().finish_node(trivia_end);
+
+    Ok(trivia)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::Skipping,
+        crate::{
+            input::StrInput,
+            literals, parse_complete, ranges,
+            rules::{Delimitted, Literal, OneOrMore, Range},
+            PegAstNode,
+        },
+    };
+
+    literals!(
+        Let: "let",
+        Semi: ";",
+        Comma: ",",
+        Space: " ",
+        WideSpace: "\u{3000}",
+    );
+    ranges!(Lower: ['a', 'z']);
+
+    type Identifier = OneOrMore<Range<Lower>>;
+
+    #[derive(Clone, Debug, pegast_derive::PegAstNode)]
+    #[pegast(skip = "Literal<Space>")]
+    struct Declaration {
+        keyword: Literal<Let>,
+        name: Identifier,
+        semicolon: Literal<Semi>,
+        #[pegast(trivia)]
+        trivia: Vec<String>,
+    }
+
+    #[derive(Clone, Debug, pegast_derive::PegAstNode)]
+    #[pegast(skip = "Literal<Space>")]
+    struct Program {
+        declarations: Vec<Declaration>,
+        #[pegast(trivia)]
+        trivia: Vec<String>,
+    }
+
+    #[derive(Clone, Debug, pegast_derive::PegAstNode)]
+    #[pegast(skip = "Literal<Space>")]
+    struct List {
+        items: Delimitted<Identifier, Literal<Comma>>,
+        #[pegast(trivia)]
+        trivia: Vec<String>,
+    }
+
+    #[derive(Clone, Debug, pegast_derive::PegAstNode)]
+    #[pegast(skip = "Literal<Space>")]
+    struct Names {
+        keyword: Literal<Let>,
+        names: OneOrMore<Identifier>,
+        semicolon: Literal<Semi>,
+        list: Option<Box<Delimitted<Identifier, Literal<Comma>>>>,
+        #[pegast(trivia)]
+        trivia: Vec<String>,
+    }
+
+    #[derive(Clone, Debug, pegast_derive::PegAstNode)]
+    #[pegast(skip = "Literal<Space>")]
+    struct Untracked {
+        keyword: Literal<Let>,
+        names: Vec<Identifier>,
+    }
+
+    #[derive(Clone, Debug, pegast_derive::PegAstNode)]
+    #[pegast(skip = "Literal<WideSpace>")]
+    struct Wide {
+        keyword: Literal<Let>,
+        name: Identifier,
+        #[pegast(trivia)]
+        trivia: Vec<String>,
+    }
+
+    #[test]
+    fn skipping_keeps_trivia_before_node() {
+        let mut input = StrInput::new("  a");
+        let node = parse_complete::<Skipping<Identifier, Literal<Space>>>(&mut input).unwrap();
+
+        assert_eq!(node.trivia.len(), 2);
+        assert_eq!(node.node.parsed_string(), "a");
+        assert_eq!(node.parsed_string(), "  a");
+    }
+
+    #[test]
+    fn skips_trivia_between_fields() {
+        let mut input = StrInput::new("let  a ;");
+        let declaration = parse_complete::<Declaration>(&mut input).unwrap();
+
+        assert_eq!(declaration.name.parsed_string(), "a");
+        assert_eq!(declaration.trivia, vec!["  ", " "]);
+        assert_eq!(declaration.parsed_string(), "let  a ;");
+        assert_eq!(declaration.parsed_string_length(), 8);
+    }
+
+    #[test]
+    fn skips_trivia_between_repetitions() {
+        let source = "let  a;  let b ;";
+        let mut input = StrInput::new(source);
+        let program = parse_complete::<Program>(&mut input).unwrap();
+
+        assert_eq!(program.declarations.len(), 2);
+        assert_eq!(program.trivia, vec!["  "]);
+        assert_eq!(program.parsed_string(), source);
+    }
+
+    #[test]
+    fn skips_trivia_around_delimitters() {
+        let source = "a , b,  c";
+        let mut input = StrInput::new(source);
+        let list = parse_complete::<List>(&mut input).unwrap();
+
+        assert_eq!(list.items.items().count(), 3);
+        assert_eq!(list.trivia, vec![" ", " ", "", "  "]);
+        assert_eq!(list.parsed_string(), source);
+    }
+
+    #[test]
+    fn skips_trivia_inside_any_repetition_type() {
+        let source = "let a b ; c , d";
+        let mut input = StrInput::new(source);
+        let names = parse_complete::<Names>(&mut input).unwrap();
+
+        assert_eq!(names.names.iter().count(), 2);
+        assert_eq!(names.list.as_ref().unwrap().items().count(), 2);
+        assert_eq!(names.parsed_string(), "let a b ; c , d");
+        assert_eq!(names.parsed_string_length(), source.len());
+    }
+
+    #[test]
+    fn drops_trivia_without_a_trivia_field() {
+        let mut input = StrInput::new("let a  b");
+        let untracked = parse_complete::<Untracked>(&mut input).unwrap();
+
+        assert_eq!(untracked.names.len(), 2);
+        assert_eq!(untracked.parsed_string(), "letab");
+        assert_eq!(untracked.parsed_string_length(), 5);
+    }
+
+    #[test]
+    fn counts_trivia_length_in_bytes() {
+        let source = "let\u{3000}a";
+        let mut input = StrInput::new(source);
+        let wide = parse_complete::<Wide>(&mut input).unwrap();
+
+        assert_eq!(wide.parsed_string(), source);
+        assert_eq!(wide.parsed_string_length(), source.len());
+    }
+}
@@ -0,0 +1,87 @@
+use {
+    crate::{
+        grammar::{Grammar, GrammarNode},
+        input::{Input, SourceSlice, Span},
+        Expected, ParseError, PegAstNode,
+    },
+    std::{
+        any::Any,
+        borrow::Cow,
+        ops::{Deref, DerefMut},
+    },
+};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Spanned<T> {
+    node: T,
+    span: Span,
+}
+
+impl<T> Spanned<T> {
+    pub fn new(node: T, span: Span) -> Self {
+        Spanned { node, span }
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn into_inner(self) -> T {
+        self.node
+    }
+}
+
+impl<T> Deref for Spanned<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.node
+    }
+}
+
+impl<T> DerefMut for Spanned<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.node
+    }
+}
+
+impl<T> PegAstNode for Spanned<T>
+where
+    T: PegAstNode,
+{
+    fn parse(input: &mut impl Input) -> Result<Self, ParseError> {
+        let start = input.position();
+        let node = T::parse(input)?;
+        let end = input.position();
+
+        Ok(Spanned::new(node, Span::new(start, end)))
+    }
+
+    fn parsed_string(&self) -> Cow<'_, str> {
+        self.node.parsed_string()
+    }
+
+    fn parsed_string_length(&self) -> usize {
+        self.node.parsed_string_length()
+    }
+
+    fn source_slice(&self) -> SourceSlice<'_> {
+        self.node.source_slice()
+    }
+
+    fn expecting() -> Vec<Expected> {
+        T::expecting()
+    }
+
+    fn grammar_node(grammar: &mut Grammar) -> GrammarNode {
+        T::grammar_node(grammar)
+    }
+
+    fn visit_nodes<'node>(&'node self, visitor: &mut dyn FnMut(&'node dyn Any))
+    where
+        Self: 'static,
+    {
+        visitor(self);
+        self.node.visit_nodes(visitor);
+    }
+}
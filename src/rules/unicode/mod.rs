@@ -0,0 +1,226 @@
+#[rustfmt::skip]
+mod tables;
+
+use {
+    super::encoded_char::SourceChar,
+    crate::{
+        grammar::{Grammar, GrammarNode},
+        input::{Input, SourceSlice},
+        Expected, ParseError, PegAstNode,
+    },
+    std::{borrow::Cow, cmp::Ordering, fmt::Debug, marker::PhantomData},
+};
+
+pub type UnicodeIdentStart = UnicodeClass<XidStart>;
+pub type UnicodeIdentContinue = UnicodeClass<XidContinue>;
+pub type UnicodeWhitespace = UnicodeClass<WhiteSpace>;
+pub type Category<C> = UnicodeClass<C>;
+
+pub trait UnicodeProperty: Debug {
+    const NAME: &'static str;
+
+    fn contains(character: char) -> bool;
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnicodeClass<P: UnicodeProperty>(SourceChar, PhantomData<P>);
+
+impl<P: UnicodeProperty> PegAstNode for UnicodeClass<P> {
+    fn parse(input: &mut impl Input) -> Result<Self, ParseError> {
+        let start = input.position();
+
+        if let Some(input_char) = input.peek() {
+            if P::contains(input_char) {
+                let character = SourceChar::next_from(input).expect("Peeked character is missing");
+                let end = input.position();
+
+                input.state().mark_token(start, end);
+
+                return Ok(UnicodeClass(character, PhantomData));
+            }
+        }
+
+        Err(ParseError::new(Self::expecting(), input.position()))
+    }
+
+    fn parsed_string(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self.0.as_str())
+    }
+
+    fn parsed_string_length(&self) -> usize {
+        self.0.as_str().len()
+    }
+
+    fn source_slice(&self) -> SourceSlice<'_> {
+        self.0.source_slice()
+    }
+
+    fn expecting() -> Vec<Expected> {
+        vec![Expected::Named(P::NAME)]
+    }
+
+    fn grammar_node(_grammar: &mut Grammar) -> GrammarNode {
+        GrammarNode::Named(P::NAME)
+    }
+}
+
+fn table_contains(table: &[(char, char)], character: char) -> bool {
+    table
+        .binary_search_by(|&(start, end)| {
+            if character < start {
+                Ordering::Greater
+            } else if character > end {
+                Ordering::Less
+            } else {
+                Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+macro_rules! unicode_properties {
+    ( $( $name:ident : $table:ident, $description:expr; )* ) => {
+        $(
+            #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+            pub enum $name {}
+
+            impl UnicodeProperty for $name {
+                const NAME: &'static str = $description;
+
+                fn contains(character: char) -> bool {
+                    table_contains(tables::$table, character)
+                }
+            }
+        )*
+    };
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WhiteSpace {}
+
+impl UnicodeProperty for WhiteSpace {
+    const NAME: &'static str = "a Unicode whitespace character";
+
+    fn contains(character: char) -> bool {
+        character.is_whitespace()
+    }
+}
+
+unicode_properties! {
+    XidStart: XID_START, "a Unicode identifier start character";
+    XidContinue: XID_CONTINUE, "a Unicode identifier character";
+    Lu: LU, "an uppercase letter (Lu)";
+    Ll: LL, "a lowercase letter (Ll)";
+    Lt: LT, "a titlecase letter (Lt)";
+    Lm: LM, "a modifier letter (Lm)";
+    Lo: LO, "an other letter (Lo)";
+    Mn: MN, "a nonspacing mark (Mn)";
+    Mc: MC, "a spacing mark (Mc)";
+    Me: ME, "an enclosing mark (Me)";
+    Nd: ND, "a decimal digit (Nd)";
+    Nl: NL, "a letter number (Nl)";
+    No: NO, "an other number (No)";
+    Pc: PC, "a connector punctuation (Pc)";
+    Pd: PD, "a dash punctuation (Pd)";
+    Ps: PS, "an open punctuation (Ps)";
+    Pe: PE, "a close punctuation (Pe)";
+    Pi: PI, "an initial punctuation (Pi)";
+    Pf: PF, "a final punctuation (Pf)";
+    Po: PO, "an other punctuation (Po)";
+    Sm: SM, "a math symbol (Sm)";
+    Sc: SC, "a currency symbol (Sc)";
+    Sk: SK, "a modifier symbol (Sk)";
+    So: SO, "an other symbol (So)";
+    Zs: ZS, "a space separator (Zs)";
+    Zl: ZL, "a line separator (Zl)";
+    Zp: ZP, "a paragraph separator (Zp)";
+    Cc: CC, "a control character (Cc)";
+    Cf: CF, "a format character (Cf)";
+    Co: CO, "a private use character (Co)";
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{
+            tables, Category, Lu, Nd, UnicodeIdentContinue, UnicodeIdentStart, UnicodeProperty,
+            XidContinue, XidStart, Zs,
+        },
+        crate::{input::StrInput, parse_complete, rules::OneOrMore, PegAstNode},
+        std::{fs, path::Path, process::Command},
+    };
+
+    #[test]
+    fn spot_checks_generated_tables() {
+        assert!(XidStart::contains('a'));
+        assert!(XidStart::contains('é'));
+        assert!(XidStart::contains('漢'));
+        assert!(!XidStart::contains('_'));
+        assert!(!XidStart::contains('1'));
+        assert!(XidContinue::contains('_'));
+        assert!(XidContinue::contains('1'));
+        assert!(XidContinue::contains('\u{301}'));
+        assert!(!XidContinue::contains('-'));
+        assert!(Lu::contains('Ω'));
+        assert!(!Lu::contains('ω'));
+        assert!(Nd::contains('٣'));
+        assert!(Zs::contains('\u{a0}'));
+        assert!(!Zs::contains('\n'));
+
+        let identifier = parse_complete::<(UnicodeIdentStart, OneOrMore<UnicodeIdentContinue>)>(
+            &mut StrInput::new("größe_2"),
+        )
+        .unwrap();
+
+        assert_eq!(identifier.parsed_string(), "größe_2");
+        assert!(parse_complete::<Category<Nd>>(&mut StrInput::new("x")).is_err());
+    }
+
+    #[test]
+    fn tables_are_sorted_and_disjoint() {
+        for table in &[
+            tables::XID_START,
+            tables::XID_CONTINUE,
+            tables::LU,
+            tables::CO,
+        ] {
+            assert!(table.iter().all(|&(start, end)| start <= end));
+            assert!(table
+                .windows(2)
+                .all(|pair| (pair[0].1 as u32) + 1 < pair[1].0 as u32));
+        }
+    }
+
+    // Regenerating takes a few seconds and needs a Python built against the
+    // same Unicode version as the committed tables, so it is skipped otherwise.
+    #[test]
+    fn tables_match_the_generator_script() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let committed = fs::read_to_string(root.join("src/rules/unicode/tables.rs")).unwrap();
+        let version = Command::new("python3")
+            .args([
+                "-c",
+                "import unicodedata; print(unicodedata.unidata_version)",
+            ])
+            .output();
+
+        match version {
+            Ok(output) if output.status.success() => {
+                let version = String::from_utf8(output.stdout).unwrap();
+
+                if !committed.lines().next().unwrap().contains(version.trim()) {
+                    return;
+                }
+            }
+            _ => return,
+        }
+
+        let generated = Command::new("python3")
+            .arg(root.join("scripts/generate_unicode_tables.py"))
+            .output()
+            .unwrap();
+
+        assert!(generated.status.success());
+        assert!(String::from_utf8(generated.stdout).unwrap() == committed);
+    }
+}
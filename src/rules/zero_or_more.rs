@@ -1,32 +1,104 @@
 use {
-    crate::{input::Input, ParseError, PegAstNode},
-    std::{borrow::Cow, iter},
+    super::skipping::{
+        parse_skipping_items, push_skipped_items, skipped_items_source_slice,
+        skipping_items_grammar_node,
+    },
+    crate::{
+        grammar::{Grammar, GrammarNode},
+        input::{Input, SourceSlice},
+        Expected, ParseError, PegAstNode,
+    },
+    std::{any::Any, borrow::Cow},
 };
 
 impl<R: PegAstNode> PegAstNode for Vec<R> {
     fn parse(input: &mut impl Input) -> Result<Self, ParseError> {
-        Ok(iter::from_fn(|| R::parse(input).ok()).collect())
+        let mut nodes = Vec::new();
+
+        loop {
+            let position = input.position();
+            let checkpoint = input.state().checkpoint();
+
+            match R::parse(input) {
+                Ok(node) => {
+                    nodes.push(node);
+
+                    if input.position() == position {
+                        return Ok(nodes);
+                    }
+                }
+                Err(error) if error.cut => return Err(error),
+                Err(error) => {
+                    input.state().rollback(checkpoint);
+                    input.state().record_failure(error);
+                    return Ok(nodes);
+                }
+            }
+        }
     }
 
     fn parsed_string(&self) -> Cow<'_, str> {
-        let mut string = String::new();
+        match self.as_slice() {
+            [] => Cow::Borrowed(""),
+            [element] => element.parsed_string(),
+            elements => self.source_slice().text_or_else(|| {
+                let mut string = String::with_capacity(self.parsed_string_length());
 
-        for element in self {
-            string.push_str(element.parsed_string().as_ref());
-        }
+                for element in elements {
+                    string.push_str(element.parsed_string().as_ref());
+                }
 
-        Cow::Owned(string)
+                string
+            }),
+        }
     }
 
     fn parsed_string_length(&self) -> usize {
         self.iter().map(PegAstNode::parsed_string_length).sum()
     }
 
-    fn expecting() -> Vec<String> {
-        let mut expecting = R::expecting();
+    fn source_slice(&self) -> SourceSlice<'_> {
+        SourceSlice::join(self.iter().map(PegAstNode::source_slice))
+    }
+
+    fn expecting() -> Vec<Expected> {
+        R::expecting()
+    }
+
+    fn grammar_node(grammar: &mut Grammar) -> GrammarNode {
+        GrammarNode::zero_or_more(R::grammar_node(grammar))
+    }
+
+    fn parse_skipping<Trivia: PegAstNode>(
+        input: &mut impl Input,
+        trivia: &mut Vec<String>,
+    ) -> Result<Self, ParseError> {
+        parse_skipping_items::<R, Trivia>(input, trivia, Vec::new())
+    }
 
-        expecting.push("or nothing".to_owned());
+    fn push_skipped<'t>(&self, string: &mut String, trivia: &mut impl Iterator<Item = &'t String>) {
+        push_skipped_items(self.iter(), string, trivia)
+    }
+
+    fn skipped_source_slice<'a>(
+        &'a self,
+        trivia: &mut impl Iterator<Item = &'a String>,
+    ) -> SourceSlice<'a> {
+        skipped_items_source_slice(self.iter(), trivia)
+    }
 
-        expecting
+    fn skipping_grammar_node<Trivia: PegAstNode>(grammar: &mut Grammar) -> GrammarNode {
+        GrammarNode::optional(skipping_items_grammar_node::<R, Trivia>(grammar))
+    }
+
+    fn visit_nodes<'node>(&'node self, visitor: &mut dyn FnMut(&'node dyn Any))
+    where
+        Self: 'static,
+    {
+        visitor(self);
+
+        for element in self {
+            element.visit_nodes(visitor);
+        }
     }
 }